@@ -0,0 +1,181 @@
+use crate::prelude::*;
+use std::panic::Location;
+
+/// Configure and build an [`Expect`].
+///
+/// Example: `Expect::builder().with_update_mode(UpdateMode::Never).build()`
+#[must_use]
+pub struct ExpectBuilder {
+    location: &'static Location<'static>,
+    settings: Settings,
+}
+
+impl ExpectBuilder {
+    /// Create a new [`ExpectBuilder`] for a test at the location.
+    pub(crate) fn new(location: &'static Location<'static>) -> Self {
+        Self {
+            location,
+            settings: Settings::load(),
+        }
+    }
+
+    /// Set the name of the snapshot directory in the directory of the test module.
+    ///
+    /// Default: `.expect`
+    pub fn with_snapshot_dir(mut self, dir: &str) -> Self {
+        dir.clone_into(&mut self.settings.snapshot_dir);
+        self
+    }
+
+    /// Set a directory of every snapshot mirroring the source tree instead of a snapshot
+    /// directory in each module.
+    ///
+    /// Example: with `tests/snapshots` the snapshots of `src/a/b.rs` are in
+    /// `tests/snapshots/a/b/`
+    pub fn with_snapshot_root<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.settings.snapshot_root = Some(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set a directory for the actual results files mirroring the snapshot layout.
+    ///
+    /// Keeps the actual results files out of the source tree.
+    ///
+    /// Example: `target/expect`
+    pub fn with_actual_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.settings.actual_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set whether only the actual results file of a mismatch is kept.
+    ///
+    /// The actual results file of a match is removed, including one left by a previous run.
+    pub fn with_actual_on_mismatch(mut self, on_mismatch: bool) -> Self {
+        self.settings.actual_on_mismatch = on_mismatch;
+        self
+    }
+
+    /// Set the extension inserted before the file extension of actual results.
+    ///
+    /// Default: `actual`
+    pub fn with_actual_extension(mut self, extension: &str) -> Self {
+        extension.clone_into(&mut self.settings.actual_ext);
+        self
+    }
+
+    /// Set the extension inserted before the file extension of expected results.
+    ///
+    /// Default: `expect`
+    pub fn with_expected_extension(mut self, extension: &str) -> Self {
+        extension.clone_into(&mut self.settings.expected_ext);
+        self
+    }
+
+    /// Set the [`Format`] of serialized snapshots.
+    #[cfg(feature = "serde")]
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.settings.format = format;
+        self
+    }
+
+    /// Set the [`YamlOptions`] for writing YAML snapshots.
+    #[cfg(feature = "yaml")]
+    pub fn with_yaml(mut self, options: YamlOptions) -> Self {
+        self.settings.yaml = options;
+        self
+    }
+
+    /// Set the [`JsonOptions`] for writing JSON snapshots.
+    #[cfg(feature = "json")]
+    pub fn with_json(mut self, options: JsonOptions) -> Self {
+        self.settings.json = options;
+        self
+    }
+
+    /// Set the number of decimal places floats are rounded to before serialized values are
+    /// compared.
+    #[cfg(feature = "serde")]
+    pub fn with_float_precision(mut self, precision: u8) -> Self {
+        self.settings.float_precision = Some(precision);
+        self
+    }
+
+    /// Add a [`Scrubber`] to apply to text before it is compared.
+    pub fn with_scrubber(mut self, scrubber: Scrubber) -> Self {
+        self.settings.scrubbers.push(scrubber);
+        self
+    }
+
+    /// Set whether mismatches are collected instead of only being returned.
+    ///
+    /// In soft mode every mismatch is recorded and the test continues. The recorded mismatches
+    /// are reported in a single panic by [`Expect::finish`] or when the [`Expect`] is dropped.
+    pub fn with_soft(mut self, soft: bool) -> Self {
+        self.settings.soft = soft;
+        self
+    }
+
+    /// Set when the expected results file is written from the actual results.
+    pub fn with_update_mode(mut self, mode: UpdateMode) -> Self {
+        self.settings.update = mode;
+        self
+    }
+
+    /// Set whether the expected results file is never written when running in CI.
+    ///
+    /// CI is detected by the `CI` environment variable.
+    pub fn with_ci_strict(mut self, strict: bool) -> Self {
+        self.settings.ci_strict = strict;
+        self
+    }
+
+    /// Set whether the snapshot directory of the module is created if it does not exist.
+    ///
+    /// Default: created unless running in CI
+    pub fn with_create_dirs(mut self, create: bool) -> Self {
+        self.settings.create_dirs = Some(create);
+        self
+    }
+
+    /// Set the [`DiffOptions`] for printing the differences of a mismatch.
+    pub fn with_diff(mut self, options: DiffOptions) -> Self {
+        self.settings.diff = options;
+        self
+    }
+
+    /// Bind the settings to the current thread while running the closure.
+    ///
+    /// Each [`Expect`] created in the closure inherits the settings, including those created by
+    /// [`Expect::new`] in shared helpers. Threads spawned in the closure do not.
+    pub fn bind<F: FnOnce() -> R, R>(self, f: F) -> R {
+        let _guard = ScopeGuard::bind(self.settings);
+        f()
+    }
+
+    /// Build the [`Expect`].
+    #[must_use]
+    pub fn build(self) -> Expect {
+        Expect::from_settings(self.location, self.settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_paths() {
+        // Arrange
+        // Act
+        let expect = Expect::builder()
+            .with_snapshot_dir(".snapshots")
+            .with_actual_extension("received")
+            .with_expected_extension("approved")
+            .build();
+        // Assert
+        assert_eq!(
+            expect.get_expected_path(TEXT_EXT),
+            PathBuf::from("src/.snapshots/builder/build_paths.approved.txt")
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use crate::prelude::*;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::iter;
+use std::process::Command;
+
+/// Arguments, exit status and output of a command.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CommandSnapshot {
+    pub args: String,
+    pub status: String,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandSnapshot {
+    /// Run the command and capture the output.
+    pub(crate) fn run(command: &mut Command) -> Result<Self, ExpectError> {
+        let args = get_args(command);
+        let output = command
+            .output()
+            .map_err(|e| ExpectError::RunCommand(e, args.clone()))?;
+        let status = output.status.code().map_or_else(
+            || "[Terminated by signal]".to_owned(),
+            |code| code.to_string(),
+        );
+        Ok(Self {
+            args,
+            status,
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+impl Display for CommandSnapshot {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_section(f, "args", &format!("{}\n", self.args))?;
+        write_section(f, "status", &format!("{}\n", self.status))?;
+        write_section(f, "stdout", &self.stdout)?;
+        write_section(f, "stderr", &self.stderr)?;
+        Ok(())
+    }
+}
+
+/// Line following the last line of a section that does not end with a newline.
+const NO_NEWLINE: &str = "\\ No newline at end of file";
+
+/// Write a named section of a document.
+///
+/// The section starts with a `--- name [n lines] ---` line followed by exactly `n` lines of the
+/// value, so a line of the value that looks like a header is not mistaken for one.
+pub(crate) fn write_section<W: fmt::Write>(out: &mut W, name: &str, value: &str) -> fmt::Result {
+    let count = value.split_inclusive('\n').count();
+    let unit = if count == 1 { "line" } else { "lines" };
+    writeln!(out, "--- {name} [{count} {unit}] ---")?;
+    out.write_str(value)?;
+    if !value.is_empty() && !value.ends_with('\n') {
+        writeln!(out)?;
+        writeln!(out, "{NO_NEWLINE}")?;
+    }
+    Ok(())
+}
+
+/// Parse the named sections of a document written by [`write_section`].
+///
+/// Lines outside of a section are collected in a section without a name.
+pub(crate) fn parse_sections(text: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let mut lines = text.split_inclusive('\n').peekable();
+    while let Some(line) = lines.next() {
+        let Some((name, count)) = parse_header(line) else {
+            match sections.last_mut() {
+                Some((name, value)) if name.is_empty() => value.push_str(line),
+                _ => sections.push((String::new(), line.to_owned())),
+            }
+            continue;
+        };
+        let mut value: String = lines.by_ref().take(count).collect();
+        if lines
+            .next_if(|line| line.strip_suffix('\n').unwrap_or(line) == NO_NEWLINE)
+            .is_some()
+        {
+            value.pop();
+        }
+        sections.push((name.to_owned(), value));
+    }
+    sections
+}
+
+/// Parse the name and number of lines of a `--- name [n lines] ---` line.
+fn parse_header(line: &str) -> Option<(&str, usize)> {
+    let (name, count) = line
+        .strip_suffix('\n')
+        .unwrap_or(line)
+        .strip_prefix("--- ")?
+        .strip_suffix("] ---")?
+        .rsplit_once(" [")?;
+    let count = count
+        .strip_suffix(" lines")
+        .or_else(|| count.strip_suffix(" line"))?;
+    Some((name, count.parse().ok()?))
+}
+
+/// Format the program and arguments of a command.
+fn get_args(command: &Command) -> String {
+    let program = command.get_program();
+    let args = command.get_args();
+    let mut output = Vec::new();
+    for arg in iter::once(program).chain(args) {
+        let arg = arg.to_string_lossy();
+        if arg.is_empty() || arg.contains(char::is_whitespace) || arg.contains('"') {
+            output.push(format!("{arg:?}"));
+        } else {
+            output.push(arg.into_owned());
+        }
+    }
+    output.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run() -> Result<(), ExpectError> {
+        // Arrange
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo out; echo err >&2; exit 3"]);
+        // Act
+        let result = CommandSnapshot::run(&mut command)?;
+        // Assert
+        assert_eq!(
+            result,
+            CommandSnapshot {
+                args: r#"sh -c "echo out; echo err >&2; exit 3""#.to_owned(),
+                status: "3".to_owned(),
+                stdout: "out\n".to_owned(),
+                stderr: "err\n".to_owned(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_sections_round_trip() {
+        // Arrange
+        let snapshot = CommandSnapshot {
+            args: "program".to_owned(),
+            status: "0".to_owned(),
+            stdout: "a\nb\n".to_owned(),
+            stderr: String::new(),
+        };
+        // Act
+        let result = parse_sections(&snapshot.to_string());
+        // Assert
+        let expected = [
+            ("args", "program\n"),
+            ("status", "0\n"),
+            ("stdout", "a\nb\n"),
+            ("stderr", ""),
+        ]
+        .map(|(name, value)| (name.to_owned(), value.to_owned()));
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_sections_header_in_value() {
+        // Arrange
+        let snapshot = CommandSnapshot {
+            args: "program".to_owned(),
+            status: "0".to_owned(),
+            stdout: "--- stderr [1 line] ---\nerr\n\n".to_owned(),
+            stderr: "\\ No newline at end of file".to_owned(),
+        };
+        // Act
+        let result = parse_sections(&snapshot.to_string());
+        // Assert
+        let expected = [
+            ("args", "program\n"),
+            ("status", "0\n"),
+            ("stdout", "--- stderr [1 line] ---\nerr\n\n"),
+            ("stderr", "\\ No newline at end of file"),
+        ]
+        .map(|(name, value)| (name.to_owned(), value.to_owned()));
+        assert_eq!(result, expected);
+    }
+}
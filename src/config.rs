@@ -0,0 +1,419 @@
+use crate::prelude::*;
+use serde::Deserialize;
+#[cfg(not(all(feature = "yaml", feature = "json")))]
+use serde::de::IgnoredAny;
+use std::env;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::Error as IoError;
+use std::sync::OnceLock;
+use toml::de::Error as TomlError;
+
+pub(crate) const CONFIG_FILE: &str = "expect.toml";
+pub(crate) const MANIFEST_FILE: &str = "Cargo.toml";
+
+/// Project-wide defaults of the [`Settings`].
+///
+/// Read from `expect.toml` in the crate root or, if it does not exist, from the
+/// `[package.metadata.expect]` table of `Cargo.toml`.
+///
+/// Example:
+///
+/// ```toml
+/// snapshot_dir = ".snapshots"
+/// format = "json"
+/// float_precision = 6
+/// update = "missing"
+/// ci_strict = true
+///
+/// [[scrubbers]]
+/// find = "/home/runner"
+/// replace = "[HOME]"
+/// ```
+///
+/// Unknown keys are rejected. Keys of disabled features are accepted but ignored.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+    snapshot_dir: Option<String>,
+    snapshot_root: Option<PathBuf>,
+    actual_dir: Option<PathBuf>,
+    actual_on_mismatch: Option<bool>,
+    actual_extension: Option<String>,
+    expected_extension: Option<String>,
+    #[cfg(feature = "serde")]
+    format: Option<Format>,
+    #[cfg(not(feature = "serde"))]
+    #[allow(dead_code)]
+    format: Option<IgnoredAny>,
+    #[cfg(feature = "yaml")]
+    yaml: Option<YamlOptions>,
+    #[cfg(not(feature = "yaml"))]
+    #[allow(dead_code)]
+    yaml: Option<IgnoredAny>,
+    #[cfg(feature = "json")]
+    json: Option<JsonOptions>,
+    #[cfg(not(feature = "json"))]
+    #[allow(dead_code)]
+    json: Option<IgnoredAny>,
+    #[cfg(feature = "serde")]
+    float_precision: Option<u8>,
+    #[cfg(not(feature = "serde"))]
+    #[allow(dead_code)]
+    float_precision: Option<IgnoredAny>,
+    update: Option<UpdateMode>,
+    ci_strict: Option<bool>,
+    create_dirs: Option<bool>,
+    scrubbers: Vec<ScrubberConfig>,
+    diff: Option<DiffOptions>,
+    soft: Option<bool>,
+}
+
+/// [`Scrubber`] that replaces every occurrence of `find` with `replace`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScrubberConfig {
+    find: String,
+    replace: String,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Option<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    metadata: Option<Metadata>,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    expect: Option<Config>,
+}
+
+impl Config {
+    /// Get the [`Config`] of the crate under test.
+    ///
+    /// The configuration is read once and shared by every test.
+    ///
+    /// Panics if the configuration can't be read, as no test can run without it.
+    #[allow(clippy::panic)]
+    pub(crate) fn get() -> &'static Config {
+        static CONFIG: OnceLock<Config> = OnceLock::new();
+        CONFIG.get_or_init(|| {
+            Config::read(&get_crate_root())
+                .unwrap_or_else(|e| panic!("{} to run test. {e}", "Failed".bold()))
+        })
+    }
+
+    /// Read the [`Config`] from `expect.toml` or `Cargo.toml` in the directory.
+    ///
+    /// If neither defines a configuration the defaults are used.
+    fn read(root: &Path) -> Result<Config, ConfigError> {
+        let path = root.join(CONFIG_FILE);
+        if path.is_file() {
+            let text = read_to_string(&path).map_err(|e| ConfigError::Read(e, path.clone()))?;
+            return Config::from_toml(&text).map_err(|e| ConfigError::Deserialize(e, path));
+        }
+        let path = root.join(MANIFEST_FILE);
+        if path.is_file() {
+            let text = read_to_string(&path).map_err(|e| ConfigError::Read(e, path.clone()))?;
+            return Config::from_manifest(&text).map_err(|e| ConfigError::Deserialize(e, path));
+        }
+        Ok(Config::default())
+    }
+
+    /// Parse the [`Config`] from the contents of `expect.toml`.
+    fn from_toml(text: &str) -> Result<Config, TomlError> {
+        toml::from_str(text)
+    }
+
+    /// Parse the [`Config`] from the `[package.metadata.expect]` table of `Cargo.toml`.
+    fn from_manifest(text: &str) -> Result<Config, TomlError> {
+        let manifest: Manifest = toml::from_str(text)?;
+        Ok(manifest
+            .package
+            .and_then(|package| package.metadata)
+            .and_then(|metadata| metadata.expect)
+            .unwrap_or_default())
+    }
+
+    /// Apply the defined values to the [`Settings`].
+    pub(crate) fn apply(&self, settings: &mut Settings) {
+        if let Some(dir) = &self.snapshot_dir {
+            dir.clone_into(&mut settings.snapshot_dir);
+        }
+        if let Some(root) = &self.snapshot_root {
+            settings.snapshot_root = Some(root.clone());
+        }
+        if let Some(dir) = &self.actual_dir {
+            settings.actual_dir = Some(dir.clone());
+        }
+        if let Some(on_mismatch) = self.actual_on_mismatch {
+            settings.actual_on_mismatch = on_mismatch;
+        }
+        if let Some(extension) = &self.actual_extension {
+            extension.clone_into(&mut settings.actual_ext);
+        }
+        if let Some(extension) = &self.expected_extension {
+            extension.clone_into(&mut settings.expected_ext);
+        }
+        #[cfg(feature = "serde")]
+        if let Some(format) = self.format {
+            settings.format = format;
+        }
+        #[cfg(feature = "yaml")]
+        if let Some(options) = &self.yaml {
+            settings.yaml = options.clone();
+        }
+        #[cfg(feature = "json")]
+        if let Some(options) = &self.json {
+            settings.json = options.clone();
+        }
+        #[cfg(feature = "serde")]
+        if let Some(precision) = self.float_precision {
+            settings.float_precision = Some(precision);
+        }
+        if let Some(mode) = self.update {
+            settings.update = mode;
+        }
+        if let Some(strict) = self.ci_strict {
+            settings.ci_strict = strict;
+        }
+        if let Some(create) = self.create_dirs {
+            settings.create_dirs = Some(create);
+        }
+        for scrubber in &self.scrubbers {
+            settings
+                .scrubbers
+                .push(Scrubber::replace(&scrubber.find, &scrubber.replace));
+        }
+        if let Some(options) = &self.diff {
+            settings.diff = options.clone();
+        }
+        if let Some(soft) = self.soft {
+            settings.soft = soft;
+        }
+    }
+}
+
+/// Error reading the [`Config`].
+#[derive(Debug)]
+pub(crate) enum ConfigError {
+    Read(IoError, PathBuf),
+    Deserialize(TomlError, PathBuf),
+}
+
+impl Display for ConfigError {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Read(e, path) => write!(
+                f,
+                "Could not read configuration file: {}\n{}",
+                path.display().to_string().dimmed(),
+                e.to_string().dimmed()
+            ),
+            ConfigError::Deserialize(e, path) => write!(
+                f,
+                "Could not deserialize configuration file: {}\n{}",
+                path.display().to_string().dimmed(),
+                e.to_string().dimmed()
+            ),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Get the root directory of the crate under test.
+///
+/// Cargo sets `CARGO_MANIFEST_DIR` when running tests, otherwise the working directory is used.
+fn get_crate_root() -> PathBuf {
+    env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from("."), PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+    use std::panic::Location;
+
+    #[test]
+    fn from_toml() -> Result<(), TomlError> {
+        // Arrange
+        let text = r#"
+snapshot_dir = ".snapshots"
+actual_dir = "target/expect"
+actual_on_mismatch = true
+float_precision = 3
+update = "never"
+ci_strict = true
+create_dirs = false
+
+[diff]
+max_lines = 10
+
+[[scrubbers]]
+find = "/home/runner"
+replace = "[HOME]"
+"#;
+        let mut settings = Settings::default();
+        // Act
+        let config = Config::from_toml(text)?;
+        config.apply(&mut settings);
+        // Assert
+        assert_eq!(settings.snapshot_dir, ".snapshots");
+        assert_eq!(
+            settings.actual_ext, ACTUAL_EXT,
+            "Undefined values keep the default"
+        );
+        assert_eq!(settings.actual_dir, Some(PathBuf::from("target/expect")));
+        assert!(settings.actual_on_mismatch);
+        #[cfg(feature = "serde")]
+        assert_eq!(settings.float_precision, Some(3));
+        assert_eq!(settings.update, UpdateMode::Never);
+        assert!(settings.ci_strict);
+        assert_eq!(settings.create_dirs, Some(false));
+        assert_eq!(settings.diff.max_lines, Some(10));
+        assert!(settings.diff.color, "Undefined options keep the default");
+        assert_eq!(settings.scrubbers.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn scrubbers() -> Result<(), ExpectError> {
+        // Arrange
+        let text = r#"
+[[scrubbers]]
+find = "/home/runner"
+replace = "[HOME]"
+"#;
+        let mut settings = Settings::default();
+        Config::from_toml(text)
+            .expect("Should deserialize")
+            .apply(&mut settings);
+        let expect = Expect::from_settings(Location::caller(), settings);
+        // Act
+        let result = expect.string("/home/runner/a.txt", TEXT_EXT)?;
+        // Assert
+        assert!(result);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn from_toml_format() -> Result<(), TomlError> {
+        // Arrange
+        let text = r#"
+format = "json_lines"
+
+[json]
+sort_keys = true
+"#;
+        let mut settings = Settings::default();
+        // Act
+        Config::from_toml(text)?.apply(&mut settings);
+        // Assert
+        assert_eq!(settings.format, Format::JsonLines);
+        assert!(settings.json.sort_keys);
+        assert_eq!(settings.json.indent, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn from_toml_unknown_key() {
+        // Arrange
+        let texts = [
+            "float_precison = 3",
+            "snapshot-dir = \".snapshots\"",
+            "[diff]\nmax_line = 10",
+            "[[scrubbers]]\nfind = \"a\"\nreplac = \"b\"",
+        ];
+        for text in texts {
+            // Act
+            let result = Config::from_toml(text);
+            // Assert
+            let error = result.expect_err("Should reject unknown key");
+            assert!(error.to_string().contains("unknown field"), "{error}");
+        }
+    }
+
+    #[test]
+    fn from_toml_disabled_feature_keys() -> Result<(), TomlError> {
+        // Arrange
+        let text = r#"
+format = "yaml"
+float_precision = 3
+
+[yaml]
+indent = 4
+
+[json]
+indent = 4
+"#;
+        // Act
+        let config = Config::from_toml(text)?;
+        // Assert
+        assert_eq!(config.snapshot_dir, None);
+        Ok(())
+    }
+
+    #[test]
+    fn from_manifest() -> Result<(), TomlError> {
+        // Arrange
+        let text = r#"
+[package]
+name = "example"
+
+[package.metadata.expect]
+snapshot_root = "tests/snapshots"
+"#;
+        let mut settings = Settings::default();
+        // Act
+        Config::from_manifest(text)?.apply(&mut settings);
+        // Assert
+        assert_eq!(
+            settings.snapshot_root,
+            Some(PathBuf::from("tests/snapshots"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_manifest_without_table() -> Result<(), TomlError> {
+        // Arrange
+        let text = "[package]\nname = \"example\"\n";
+        let mut settings = Settings::default();
+        // Act
+        Config::from_manifest(text)?.apply(&mut settings);
+        // Assert
+        assert_eq!(settings.snapshot_dir, EXPECT_DIR);
+        Ok(())
+    }
+
+    #[test]
+    fn read_invalid() {
+        // Arrange
+        let root = temp_dir().join("expect_config_read_invalid");
+        create_dir_all(&root).expect("Should be able to create directory");
+        write(root.join(CONFIG_FILE), "update = 1").expect("Should be able to write file");
+        // Act
+        let result = Config::read(&root);
+        // Assert
+        let error = result.expect_err("Should fail to deserialize");
+        assert!(matches!(error, ConfigError::Deserialize(_, _)));
+        assert!(error.to_string().contains(CONFIG_FILE), "{error}");
+    }
+
+    #[test]
+    fn read_crate_root() -> Result<(), ConfigError> {
+        // Arrange
+        // Act
+        let config = Config::read(&get_crate_root())?;
+        // Assert
+        assert_eq!(config.snapshot_dir, None);
+        Ok(())
+    }
+}
@@ -17,6 +17,10 @@ pub(crate) struct TestContext {
     /// Line number of the test.
     #[allow(dead_code)]
     pub(crate) line: u32,
+    /// Label of the parameterized case.
+    ///
+    /// Snapshots of a case are grouped in a directory for the test.
+    pub(crate) case: Option<String>,
 }
 
 impl TestContext {
@@ -26,25 +30,60 @@ impl TestContext {
             .name()
             .expect("Should be able to get test name")
             .to_owned();
-        let components: Vec<String> = name.split("::").map(ToOwned::to_owned).collect();
-        let module = components
-            .iter()
-            .take(components.len() - 1)
-            .map(ToOwned::to_owned)
-            .collect();
+        Self::from_thread_name(&name, location)
+    }
+
+    /// Create a new [`TestContext`] from the name of a test thread.
+    ///
+    /// Parameterized cases generated by `rstest` are recognized by the `case_1` or
+    /// `case_1_description` suffix. A test directly in a `tests` module is never a case, so a
+    /// test function named `case_1` keeps its name.
+    ///
+    /// Other generated names, such as those of the `test-case` crate, are not recognized.
+    /// Use [`Expect::case`](crate::Expect::case) to label them.
+    fn from_thread_name(name: &str, location: &Location) -> Self {
+        let mut components: Vec<String> = name.split("::").map(ToOwned::to_owned).collect();
+        let mut case = None;
+        let parent = components
+            .len()
+            .checked_sub(2)
+            .and_then(|i| components.get(i));
+        if parent.is_some_and(|parent| parent != TESTS_MODULE) {
+            case = components.last().filter(|last| is_case_name(last)).cloned();
+            if case.is_some() {
+                components.pop();
+            }
+        }
         let name = components
-            .last()
-            .expect("Should be at least one component in test name")
-            .to_owned();
+            .pop()
+            .expect("Should be at least one component in test name");
         Self {
-            module,
+            module: components,
             name,
             file: PathBuf::from(location.file()),
             line: location.line(),
+            case,
         }
     }
 }
 
+/// Conventional name of the module containing the tests of a file.
+const TESTS_MODULE: &str = "tests";
+
+/// Is the name an `rstest` case such as `case_2` or `case_2_empty_input`?
+///
+/// The whole name is used as the label, so cases with the same description don't collide.
+fn is_case_name(name: &str) -> bool {
+    let Some(rest) = name.strip_prefix("case_") else {
+        return false;
+    };
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    digits > 0
+        && rest
+            .get(digits..)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,5 +106,31 @@ mod tests {
         assert_eq!(context.file, PathBuf::from("src/context.rs"), "File path");
         assert!(context.file.is_file(), "File path");
         assert_eq!(context.line, line, "Line number");
+        assert_eq!(context.case, None, "Case");
+    }
+
+    #[test]
+    fn from_thread_name_case() {
+        // Arrange
+        let location = Location::caller();
+        // Act
+        let described =
+            TestContext::from_thread_name("a::b::test_fn::case_1_empty_input", location);
+        let numbered = TestContext::from_thread_name("a::test_fn::case_12", location);
+        let other = TestContext::from_thread_name("a::test_fn::cases", location);
+        let same_description =
+            TestContext::from_thread_name("a::b::test_fn::case_2_empty_input", location);
+        let plain = TestContext::from_thread_name("a::tests::case_1", location);
+        // Assert
+        assert_eq!(described.module, vec!["a".to_owned(), "b".to_owned()]);
+        assert_eq!(described.name, "test_fn");
+        assert_eq!(described.case.as_deref(), Some("case_1_empty_input"));
+        assert_eq!(same_description.case.as_deref(), Some("case_2_empty_input"));
+        assert_eq!(plain.name, "case_1");
+        assert_eq!(plain.case, None);
+        assert_eq!(numbered.name, "test_fn");
+        assert_eq!(numbered.case.as_deref(), Some("case_12"));
+        assert_eq!(other.name, "cases");
+        assert_eq!(other.case, None);
     }
 }
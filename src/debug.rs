@@ -0,0 +1,195 @@
+const INDENT: usize = 4;
+const ADDRESS: &str = "0x[address]";
+/// Minimum number of hexadecimal digits of a pointer address.
+///
+/// Shorter values such as `0xff` are more likely to be numbers than addresses.
+const MIN_ADDRESS_DIGITS: usize = 8;
+
+/// Normalize the pretty `Debug` output so it is deterministic.
+///
+/// Pointer addresses are replaced with `0x[address]` and the entries of every map and set
+/// are sorted. Maps and sets can't be told apart from ordered collections such as `BTreeMap`
+/// so their entries are sorted too.
+pub(crate) fn normalize_debug(debug: &str) -> String {
+    let debug = replace_addresses(debug);
+    let lines: Vec<&str> = debug.lines().collect();
+    normalize_lines(&lines).join("\n")
+}
+
+/// Replace hexadecimal pointer addresses.
+///
+/// An address is `0x` followed by lowercase hexadecimal digits as formatted by pointers.
+fn replace_addresses(debug: &str) -> String {
+    let mut output = String::with_capacity(debug.len());
+    let mut rest = debug;
+    while let Some(index) = rest.find("0x") {
+        let (before, after) = rest.split_at(index);
+        let digits = after
+            .get(2..)
+            .unwrap_or_default()
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || matches!(c, 'a'..='f'))
+            .count();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let is_within_word = before.chars().next_back().is_some_and(is_word)
+            || after
+                .get(2 + digits..)
+                .unwrap_or_default()
+                .chars()
+                .next()
+                .is_some_and(is_word);
+        output.push_str(before);
+        if digits < MIN_ADDRESS_DIGITS || is_within_word {
+            output.push_str("0x");
+            rest = after.get(2..).unwrap_or_default();
+        } else {
+            output.push_str(ADDRESS);
+            rest = after.get(2 + digits..).unwrap_or_default();
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Sort the entries of every map or set block.
+fn normalize_lines(lines: &[&str]) -> Vec<String> {
+    let mut output = Vec::new();
+    let mut index = 0;
+    while let Some(line) = lines.get(index) {
+        output.push((*line).to_owned());
+        index += 1;
+        if !is_unordered_block(line) {
+            continue;
+        }
+        let indent = get_indent(line);
+        let end = lines
+            .iter()
+            .skip(index)
+            .position(|line| get_indent(line) <= indent)
+            .map_or(lines.len(), |position| index + position);
+        let body = lines.get(index..end).unwrap_or_default();
+        let mut entries: Vec<String> = split_entries(body, indent + INDENT)
+            .into_iter()
+            .map(|entry| normalize_lines(entry).join("\n"))
+            .collect();
+        entries.sort();
+        output.extend(entries);
+        index = end;
+    }
+    output
+}
+
+/// Split the body of a block into the lines of each entry.
+fn split_entries<'a, 'b>(body: &'a [&'b str], indent: usize) -> Vec<&'a [&'b str]> {
+    let mut entries = Vec::new();
+    let mut start = 0;
+    for (index, line) in body.iter().enumerate() {
+        if index > start && get_indent(line) == indent && !is_closing(line) {
+            entries.push(body.get(start..index).unwrap_or_default());
+            start = index;
+        }
+    }
+    if start < body.len() {
+        entries.push(body.get(start..).unwrap_or_default());
+    }
+    entries
+}
+
+/// Is the line the start of a map or set?
+///
+/// Unlike structs, maps and sets have no type name before the opening brace.
+fn is_unordered_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed == "{" || trimmed.ends_with(": {")
+}
+
+fn is_closing(line: &str) -> bool {
+    line.trim_start().starts_with(['}', ']', ')'])
+}
+
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap, HashSet};
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Sample {
+        pointer: *const u8,
+        map: HashMap<&'static str, Vec<u32>>,
+        set: HashSet<u32>,
+        nested: HashMap<u32, HashMap<u32, u32>>,
+    }
+
+    fn sample() -> Sample {
+        let value = 1_u8;
+        Sample {
+            pointer: &raw const value,
+            map: HashMap::from([("b", vec![2]), ("a", vec![1]), ("c", Vec::new())]),
+            set: HashSet::from([3, 1, 2]),
+            nested: HashMap::from([(2, HashMap::from([(4, 4), (3, 3)])), (1, HashMap::new())]),
+        }
+    }
+
+    #[test]
+    fn normalize_debug_addresses() {
+        // Arrange
+        let debug =
+            "a: 0x7ffd1234abcd, b: 0x, c: a0x12345678, d: 0xff, e: 0xDEADBEEF, f: 0x12345678_u32";
+        // Act
+        let result = normalize_debug(debug);
+        // Assert
+        assert_eq!(
+            result,
+            "a: 0x[address], b: 0x, c: a0x12345678, d: 0xff, e: 0xDEADBEEF, f: 0x12345678_u32"
+        );
+    }
+
+    #[test]
+    fn normalize_debug_sample() {
+        // Arrange
+        let debug = format!("{:#?}", sample());
+        // Act
+        let result = normalize_debug(&debug);
+        // Assert
+        let expected = r#"Sample {
+    pointer: 0x[address],
+    map: {
+        "a": [
+            1,
+        ],
+        "b": [
+            2,
+        ],
+        "c": [],
+    },
+    set: {
+        1,
+        2,
+        3,
+    },
+    nested: {
+        1: {},
+        2: {
+            3: 3,
+            4: 4,
+        },
+    },
+}"#;
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn normalize_debug_sorts_ordered_maps() {
+        // Arrange
+        let debug = format!("{:#?}", BTreeMap::from([(2, "b"), (10, "a")]));
+        // Act
+        let result = normalize_debug(&debug);
+        // Assert
+        assert_eq!(result, "{\n    10: \"a\",\n    2: \"b\",\n}");
+    }
+}
@@ -1,33 +1,140 @@
 use crate::prelude::*;
+use std::cell::RefCell;
 
-pub(crate) struct Diff;
+/// Compare actual and expected values and print the differences.
+#[derive(Default)]
+pub(crate) struct Diff {
+    options: DiffOptions,
+    /// Output captured instead of printed.
+    captured: Option<RefCell<String>>,
+}
 
 impl Diff {
+    /// Create a new [`Diff`].
+    pub(crate) fn new(options: DiffOptions) -> Self {
+        Self {
+            options,
+            captured: None,
+        }
+    }
+
+    /// Create a [`Diff`] that captures the output instead of printing it.
+    #[cfg(test)]
+    pub(crate) fn capture(options: DiffOptions) -> Self {
+        Self {
+            options,
+            captured: Some(RefCell::default()),
+        }
+    }
+
+    /// Get the captured output.
+    #[cfg(test)]
+    pub(crate) fn get_output(&self) -> String {
+        self.captured
+            .as_ref()
+            .map(|captured| captured.borrow().clone())
+            .unwrap_or_default()
+    }
+
     /// Compare strings and print the differences.
-    pub(crate) fn string(actual: &str, expected: &str) -> bool {
+    pub(crate) fn string(&self, actual: &str, expected: &str) -> bool {
         let is_success = actual == expected;
         if !is_success {
-            print_actual(actual);
-            print_expected(expected);
+            self.print_actual(actual);
+            self.print_expected(expected);
+        }
+        is_success
+    }
+
+    /// Compare file contents as text if possible, otherwise as bytes.
+    pub(crate) fn file(&self, actual: &[u8], expected: &[u8]) -> bool {
+        match (str::from_utf8(actual), str::from_utf8(expected)) {
+            (Ok(actual), Ok(expected)) => self.string(actual, expected),
+            _ => self.bytes(actual, expected),
+        }
+    }
+
+    /// Compare named sections and print the differences of each.
+    pub(crate) fn sections(
+        &self,
+        actual: &[(String, String)],
+        expected: &[(String, String)],
+    ) -> bool {
+        let mut names: Vec<&String> = actual.iter().map(|(name, _)| name).collect();
+        for (name, _) in expected {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        let mut is_success = true;
+        for name in names {
+            let actual = get_section(actual, name);
+            let expected = get_section(expected, name);
+            match (actual, expected) {
+                (Some(actual), Some(expected)) if actual != expected => {
+                    self.print_line(&format!("Changed: {name}"));
+                    self.print_actual(actual);
+                    self.print_expected(expected);
+                }
+                (Some(actual), None) => {
+                    self.print_line(&format!("Added: {name}"));
+                    self.print_actual(actual);
+                }
+                (None, Some(expected)) => {
+                    self.print_line(&format!("Removed: {name}"));
+                    self.print_expected(expected);
+                }
+                _ => continue,
+            }
+            is_success = false;
         }
         is_success
     }
 
+    /// Compare bytes and print a hex dump of the differences.
+    pub(crate) fn bytes(&self, actual: &[u8], expected: &[u8]) -> bool {
+        let Some(offset) = first_difference(actual, expected) else {
+            return true;
+        };
+        self.print_line(&format!(
+            "Size: {} bytes actual, {} bytes expected",
+            actual.len(),
+            expected.len()
+        ));
+        self.print_line(&format!("First difference at offset: {offset:#010x}"));
+        let rows = count_hex_rows(actual).max(count_hex_rows(expected));
+        for row in 0..rows {
+            let actual_row = format_hex_row(actual, row);
+            let expected_row = format_hex_row(expected, row);
+            if actual_row != expected_row {
+                self.print_actual(actual_row.as_deref().unwrap_or("[No bytes at offset]"));
+                self.print_expected(expected_row.as_deref().unwrap_or("[No bytes at offset]"));
+            }
+        }
+        false
+    }
+
     /// Compare with [`PartialEq`] and print the differences.
-    pub(crate) fn value<T: Debug + DeserializeOwned + PartialEq + Serialize>(
+    #[cfg(feature = "serde")]
+    pub(crate) fn value<S: Serializer, T: Debug + DeserializeOwned + PartialEq + Serialize>(
+        &self,
+        serializer: &S,
         actual: &T,
         expected: &T,
     ) -> bool {
         let is_success = actual == expected;
         if !is_success {
-            print_actual(&display_value(&actual));
-            print_expected(&display_value(&expected));
+            self.print_actual(&display_value(serializer, &actual));
+            self.print_expected(&display_value(serializer, &expected));
         }
         is_success
     }
 
     /// Compare each value with [`PartialEq`] and print the differences.
-    pub(crate) fn values<T: Debug + DeserializeOwned + PartialEq + Serialize>(
+    #[cfg(feature = "serde")]
+    pub(crate) fn values<S: Serializer, T: Debug + DeserializeOwned + PartialEq + Serialize>(
+        &self,
+        serializer: &S,
         actual: &[T],
         expected: &[T],
     ) -> bool {
@@ -39,44 +146,80 @@ impl Diff {
         let mut is_success = true;
         for i in 0..max {
             let Some(actual_item) = actual.get(i) else {
-                println!("Index {i}");
+                self.print_line(&serializer.get_item_label(i));
                 let expected_item = expected.get(i).expect("Verified item should exist");
-                print_actual("[No item at index]");
-                print_expected(&display_value(&expected_item));
+                self.print_actual("[No item at index]");
+                self.print_expected(&display_value(serializer, &expected_item));
                 is_success = false;
                 continue;
             };
             let Some(expected_item) = expected.get(i) else {
-                println!("Index {i}");
-                print_actual(&display_value(&actual_item));
-                print_expected("[No item at index]");
+                self.print_line(&serializer.get_item_label(i));
+                self.print_actual(&display_value(serializer, &actual_item));
+                self.print_expected("[No item at index]");
                 is_success = false;
                 continue;
             };
             if actual_item != expected_item {
-                println!("Index {i}");
-                print_actual(&display_value(&actual_item));
-                print_expected(&display_value(&expected_item));
+                self.print_line(&serializer.get_item_label(i));
+                self.print_actual(&display_value(serializer, &actual_item));
+                self.print_expected(&display_value(serializer, &expected_item));
                 is_success = false;
             }
         }
         is_success
     }
+
+    fn print_actual(&self, actual: &str) {
+        self.print(actual, Color::Red);
+    }
+
+    fn print_expected(&self, expected: &str) {
+        self.print(expected, Color::Green);
+    }
+
+    /// Print the text truncated to the maximum number of lines.
+    fn print(&self, text: &str, color: Color) {
+        let text = match self.options.max_lines {
+            Some(max) if text.lines().count() > max => {
+                let remaining = text.lines().count() - max;
+                let lines: Vec<&str> = text.lines().take(max).collect();
+                format!("{}\n[{remaining} more lines]", lines.join("\n"))
+            }
+            _ => text.to_owned(),
+        };
+        if self.options.color {
+            self.print_line(&text.color(color).to_string());
+        } else {
+            self.print_line(&text);
+        }
+    }
+
+    /// Print a line, or capture it.
+    fn print_line(&self, line: &str) {
+        match &self.captured {
+            Some(captured) => {
+                let mut captured = captured.borrow_mut();
+                captured.push_str(line);
+                captured.push('\n');
+            }
+            None => println!("{line}"),
+        }
+    }
 }
 
-fn display_value<T: Debug + Serialize>(value: &T) -> String {
-    let serializer = DefaultSerializer::default();
+#[cfg(feature = "serde")]
+fn display_value<S: Serializer, T: Debug + Serialize>(serializer: &S, value: &T) -> String {
     serializer
         .serialize_to_string(value)
         .unwrap_or_else(|_| format!("{value:?}"))
 }
 
-fn print_actual(actual: &str) {
-    println!("{}", actual.red());
-}
-
-fn print_expected(expected: &str) {
-    println!("{}", expected.green());
+fn get_section<'a>(sections: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    sections
+        .iter()
+        .find(|(section, _)| section == name)
+        .map(|(_, value)| value.as_str())
 }
 
 #[cfg(test)]
@@ -90,11 +233,67 @@ mod tests {
         let invalid = "Oh, no!";
         // Act
         // Assert
-        assert!(Diff::string(valid, valid), "Valid");
-        assert!(!Diff::string(invalid, valid), "Invalid");
+        assert!(Diff::default().string(valid, valid), "Valid");
+        assert!(!Diff::default().string(invalid, valid), "Invalid");
     }
 
     #[test]
+    fn file() {
+        // Arrange
+        let text = b"Hello, world!";
+        let binary = b"Hello, world!\xff";
+        // Act
+        // Assert
+        assert!(Diff::default().file(text, text), "Valid text");
+        assert!(Diff::default().file(binary, binary), "Valid binary");
+        assert!(!Diff::default().file(binary, text), "Invalid");
+    }
+
+    #[test]
+    fn sections() {
+        // Arrange
+        let section = |name: &str, value: &str| (name.to_owned(), value.to_owned());
+        let valid = [section("stdout", "a"), section("stderr", "b")];
+        let invalid = [section("stdout", "a"), section("stderr", "c")];
+        let missing = [section("stdout", "a")];
+        // Act
+        // Assert
+        assert!(Diff::default().sections(&valid, &valid), "Valid");
+        assert!(!Diff::default().sections(&invalid, &valid), "Invalid");
+        assert!(!Diff::default().sections(&missing, &valid), "Missing");
+    }
+
+    #[test]
+    fn string_options() {
+        // Arrange
+        let diff = Diff::capture(DiffOptions {
+            color: false,
+            max_lines: Some(1),
+        });
+        // Act
+        let result = diff.string("a\nb\nc", "a\nb");
+        // Assert
+        assert!(!result, "Invalid");
+        assert_eq!(
+            diff.get_output(),
+            "a\n[2 more lines]\na\n[1 more lines]\n",
+            "Truncated without color"
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        // Arrange
+        let valid = b"\x00\x01Hello, world!\xff";
+        let invalid = b"\x00\x01Hello, World!";
+        // Act
+        // Assert
+        assert!(Diff::default().bytes(valid, valid), "Valid");
+        assert!(!Diff::default().bytes(invalid, valid), "Invalid");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
     fn value() {
         // Arrange
         let valid = SampleStruct::sample();
@@ -102,13 +301,19 @@ mod tests {
             string: "INVALID".to_owned(),
             ..SampleStruct::sample()
         };
+        let serializer = FormatSerializer::default();
         // Act
         // Assert
-        assert!(Diff::value(&valid, &valid), "Valid");
-        assert!(!Diff::value(&invalid, &valid), "Invalid");
+        assert!(Diff::default().value(&serializer, &valid, &valid), "Valid");
+        assert!(
+            !Diff::default().value(&serializer, &invalid, &valid),
+            "Invalid"
+        );
     }
 
     #[test]
+    #[cfg(feature = "serde")]
+    #[allow(clippy::useless_vec, clippy::cloned_ref_to_slice_refs)]
     fn values() {
         // Arrange
         let valid = SampleStruct::sample();
@@ -116,25 +321,36 @@ mod tests {
             string: "INVALID".to_owned(),
             ..SampleStruct::sample()
         };
+        let serializer = FormatSerializer::default();
         // Act
         // Assert
         assert!(
-            Diff::values(
+            Diff::default().values(
+                &serializer,
                 &vec![valid.clone(), valid.clone()],
                 &vec![valid.clone(), valid.clone()]
             ),
             "Valid"
         );
         assert!(
-            !Diff::values(&[valid.clone()], &vec![valid.clone(), valid.clone()]),
+            !Diff::default().values(
+                &serializer,
+                &[valid.clone()],
+                &vec![valid.clone(), valid.clone()]
+            ),
             "Missing on actual"
         );
         assert!(
-            !Diff::values(&vec![valid.clone(), valid.clone()], &[valid.clone()]),
+            !Diff::default().values(
+                &serializer,
+                &vec![valid.clone(), valid.clone()],
+                &[valid.clone()]
+            ),
             "Missing on expected"
         );
         assert!(
-            !Diff::values(
+            !Diff::default().values(
+                &serializer,
                 &vec![valid.clone(), invalid.clone()],
                 &vec![valid.clone(), valid.clone()]
             ),
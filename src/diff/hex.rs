@@ -0,0 +1,81 @@
+use std::fmt::Write as _;
+
+const ROW_WIDTH: usize = 16;
+
+/// Format a row of a hex dump.
+///
+/// Example: `00000010  48 65 6c 6c 6f                                   |Hello|`
+pub(crate) fn format_hex_row(bytes: &[u8], row: usize) -> Option<String> {
+    let start = row * ROW_WIDTH;
+    let rest = bytes.get(start..).filter(|rest| !rest.is_empty())?;
+    let chunk = rest.get(..ROW_WIDTH).unwrap_or(rest);
+    let mut output = format!("{start:08x} ");
+    for index in 0..ROW_WIDTH {
+        match chunk.get(index) {
+            Some(byte) => write!(output, " {byte:02x}").expect("Should write to string"),
+            None => output.push_str("   "),
+        }
+    }
+    output.push_str("  |");
+    for byte in chunk {
+        let c = char::from(*byte);
+        output.push(if c.is_ascii_graphic() || c == ' ' {
+            c
+        } else {
+            '.'
+        });
+    }
+    output.push('|');
+    Some(output)
+}
+
+/// Get the number of hex dump rows required for the bytes.
+pub(crate) fn count_hex_rows(bytes: &[u8]) -> usize {
+    bytes.len().div_ceil(ROW_WIDTH)
+}
+
+/// Get the offset of the first byte that differs.
+pub(crate) fn first_difference(actual: &[u8], expected: &[u8]) -> Option<usize> {
+    actual
+        .iter()
+        .zip(expected)
+        .position(|(a, b)| a != b)
+        .or_else(|| (actual.len() != expected.len()).then(|| actual.len().min(expected.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_hex_row_partial() {
+        // Arrange
+        let bytes = b"0123456789abcdefHello\n";
+        // Act
+        let first = format_hex_row(bytes, 0);
+        let second = format_hex_row(bytes, 1);
+        let third = format_hex_row(bytes, 2);
+        // Assert
+        assert_eq!(
+            first.as_deref(),
+            Some("00000000  30 31 32 33 34 35 36 37 38 39 61 62 63 64 65 66  |0123456789abcdef|")
+        );
+        assert_eq!(
+            second.as_deref(),
+            Some("00000010  48 65 6c 6c 6f 0a                                |Hello.|")
+        );
+        assert_eq!(third, None);
+        assert_eq!(count_hex_rows(bytes), 2);
+    }
+
+    #[test]
+    fn first_difference_offset() {
+        // Arrange
+        // Act
+        // Assert
+        assert_eq!(first_difference(b"abc", b"abc"), None, "Equal");
+        assert_eq!(first_difference(b"abc", b"axc"), Some(1), "Modified");
+        assert_eq!(first_difference(b"abc", b"ab"), Some(2), "Longer");
+        assert_eq!(first_difference(b"", b"a"), Some(0), "Shorter");
+    }
+}
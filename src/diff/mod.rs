@@ -1,2 +1,6 @@
 mod diff;
+mod hex;
+mod options;
 pub(crate) use diff::*;
+pub(crate) use hex::*;
+pub use options::*;
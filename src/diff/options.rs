@@ -0,0 +1,25 @@
+#[cfg(feature = "config")]
+use serde::Deserialize;
+
+/// Options for printing the differences of a mismatch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct DiffOptions {
+    /// Print the actual values in red and the expected values in green.
+    pub color: bool,
+    /// Maximum number of lines to print for each value.
+    pub max_lines: Option<usize>,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            color: true,
+            max_lines: None,
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::prelude::*;
+
+/// Offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+/// Prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Create a manifest of the files in a directory.
+///
+/// Each file is a section named by its relative path containing the size and, if it is text, the
+/// contents. Binary files have a hash of their contents so a change of the same size is
+/// detected. Symbolic links are listed with their target.
+///
+/// Text is scrubbed before the size is counted so the manifest stays deterministic.
+pub(crate) fn get_manifest<F: Fn(&str) -> String>(
+    root: &Path,
+    scrub: F,
+) -> Result<String, ExpectError> {
+    let mut manifest = String::new();
+    for (relative, path) in get_files(root)? {
+        let value = if path.is_symlink() {
+            let target = read_link(&path).map_err(|e| ExpectError::ReadFile(e, path.clone()))?;
+            format!("[symlink to {}]\n", scrub(&target.to_string_lossy()))
+        } else {
+            let bytes = read(&path).map_err(|e| ExpectError::ReadFile(e, path.clone()))?;
+            match get_text(&bytes) {
+                Some(text) => {
+                    let text = scrub(text);
+                    format!("[{} bytes]\n{text}", text.len())
+                }
+                None => format!(
+                    "[{} bytes, binary, fnv1a64:{:016x}]\n",
+                    bytes.len(),
+                    get_hash(&bytes)
+                ),
+            }
+        };
+        write_section(&mut manifest, &relative, &value).expect("Should write to string");
+    }
+    Ok(manifest)
+}
+
+/// Get the relative path and path of each file in a directory sorted by the relative path.
+///
+/// Symbolic links are included but not followed, so a link to a parent directory can't recurse.
+pub(crate) fn get_files(root: &Path) -> Result<Vec<(String, PathBuf)>, ExpectError> {
+    let mut files = Vec::new();
+    collect_files(root, &mut files)?;
+    let mut entries: Vec<(String, PathBuf)> = files
+        .into_iter()
+        .map(|path| {
+            let relative = path
+                .strip_prefix(root)
+                .expect("File should be in the directory")
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            (relative, path)
+        })
+        .collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(entries)
+}
+
+/// Recursively collect the paths of each file in the directory.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), ExpectError> {
+    let entries = read_dir(dir).map_err(|e| ExpectError::ReadDirectory(e, dir.to_path_buf()))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| ExpectError::ReadDirectory(e, dir.to_path_buf()))?;
+        let file_type = entry
+            .file_type()
+            .map_err(|e| ExpectError::ReadDirectory(e, dir.to_path_buf()))?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// Get the bytes as text if they are valid UTF-8 without null bytes.
+pub(crate) fn get_text(bytes: &[u8]) -> Option<&str> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    str::from_utf8(bytes).ok()
+}
+
+/// Get the 64-bit FNV-1a hash of the bytes.
+///
+/// The hash is stable across platforms and Rust versions, unlike the standard library hashers.
+fn get_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+    #[cfg(unix)]
+    use std::os::unix::fs::symlink;
+
+    #[test]
+    fn get_manifest_files() -> Result<(), ExpectError> {
+        // Arrange
+        let root = temp_dir().join("expect_get_manifest_files");
+        if root.exists() {
+            remove_dir_all(&root).expect("Should be able to remove directory");
+        }
+        create_dir_all(root.join("nested")).expect("Should be able to create directory");
+        write(root.join("b.txt"), "Hello, world!\n").expect("Should be able to write file");
+        write(root.join("nested/a.txt"), "").expect("Should be able to write file");
+        write(root.join("data.bin"), [0, 1, 2]).expect("Should be able to write file");
+        // Act
+        let result = get_manifest(&root, ToOwned::to_owned)?;
+        // Assert
+        let expected = "--- b.txt [2 lines] ---
+[14 bytes]
+Hello, world!
+--- data.bin [1 line] ---
+[3 bytes, binary, fnv1a64:d949aa186c0c4928]
+--- nested/a.txt [1 line] ---
+[0 bytes]
+";
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn get_manifest_binary_change() -> Result<(), ExpectError> {
+        // Arrange
+        let root = temp_dir().join("expect_get_manifest_binary_change");
+        if root.exists() {
+            remove_dir_all(&root).expect("Should be able to remove directory");
+        }
+        create_dir_all(&root).expect("Should be able to create directory");
+        write(root.join("data.bin"), [0, 1, 2]).expect("Should be able to write file");
+        let before = get_manifest(&root, ToOwned::to_owned)?;
+        write(root.join("data.bin"), [0, 1, 3]).expect("Should be able to write file");
+        // Act
+        let after = get_manifest(&root, ToOwned::to_owned)?;
+        // Assert
+        assert_ne!(before, after);
+        Ok(())
+    }
+
+    #[test]
+    fn get_manifest_content() -> Result<(), ExpectError> {
+        // Arrange
+        let root = temp_dir().join("expect_get_manifest_content");
+        if root.exists() {
+            remove_dir_all(&root).expect("Should be able to remove directory");
+        }
+        create_dir_all(&root).expect("Should be able to create directory");
+        write(root.join("a.txt"), "--- b.txt [1 line] ---\nb").expect("Should write file");
+        write(root.join("b.txt"), "b\n").expect("Should be able to write file");
+        // Act
+        let result = parse_sections(&get_manifest(&root, ToOwned::to_owned)?);
+        // Assert
+        let expected = [
+            ("a.txt", "[24 bytes]\n--- b.txt [1 line] ---\nb"),
+            ("b.txt", "[2 bytes]\nb\n"),
+        ]
+        .map(|(name, value)| (name.to_owned(), value.to_owned()));
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn get_manifest_scrub() -> Result<(), ExpectError> {
+        // Arrange
+        let root = temp_dir().join("expect_get_manifest_scrub");
+        if root.exists() {
+            remove_dir_all(&root).expect("Should be able to remove directory");
+        }
+        create_dir_all(&root).expect("Should be able to create directory");
+        write(root.join("a.txt"), "Started at 12:00:00.123456\n")
+            .expect("Should be able to write file");
+        // Act
+        let result = get_manifest(&root, |text| text.replace("12:00:00.123456", "[TIME]"))?;
+        // Assert
+        assert_eq!(
+            result,
+            "--- a.txt [2 lines] ---\n[18 bytes]\nStarted at [TIME]\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn get_manifest_symlink() -> Result<(), ExpectError> {
+        // Arrange
+        let root = temp_dir().join("expect_get_manifest_symlink");
+        if root.exists() {
+            remove_dir_all(&root).expect("Should be able to remove directory");
+        }
+        create_dir_all(root.join("nested")).expect("Should be able to create directory");
+        symlink("..", root.join("nested/parent")).expect("Should be able to create symlink");
+        // Act
+        let result = get_manifest(&root, ToOwned::to_owned)?;
+        // Assert
+        assert_eq!(result, "--- nested/parent [1 line] ---\n[symlink to ..]\n");
+        Ok(())
+    }
+}
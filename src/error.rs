@@ -10,16 +10,27 @@ pub enum ExpectError {
     CreateSubDir(std::io::Error, PathBuf),
     WriteActual(std::io::Error),
     CreateActual(std::io::Error, PathBuf),
-    SerializeActual(Box<dyn Error>),
+    SerializeActual(Box<dyn Error + Send + Sync>),
     FlushActual(std::io::Error),
-    CopyActual(std::io::Error, PathBuf, PathBuf),
+    RemoveActual(std::io::Error, PathBuf),
+    DeserializeActual(Box<dyn Error + Send + Sync>),
     OpenExpected(std::io::Error, PathBuf),
+    WriteExpected(std::io::Error, PathBuf),
     ReadExpected(std::io::Error),
-    DeserializeExpected(Box<dyn Error>),
+    DeserializeExpected(Box<dyn Error + Send + Sync>),
+    RunCommand(std::io::Error, String),
+    ReadDirectory(std::io::Error, PathBuf),
+    ReadFile(std::io::Error, PathBuf),
+    NoFixtures(PathBuf),
+    ExpectedNotFound(PathBuf),
 }
 
 impl Display for ExpectError {
-    #[allow(clippy::absolute_paths)]
+    #[allow(
+        clippy::absolute_paths,
+        clippy::too_many_lines,
+        clippy::unnecessary_trailing_comma
+    )]
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let message = match self {
             ExpectError::ExpectDirNotFound(path) => {
@@ -47,17 +58,29 @@ impl Display for ExpectError {
             ExpectError::FlushActual(e) => {
                 format!("Could not flush actual results file.\n{}", format_error(e),)
             }
-            ExpectError::CopyActual(e, actual, expected) => {
+            ExpectError::RemoveActual(e, path) => {
                 format!(
-                    "Could not copy actual results file.\nFrom: {}\nTo: {}\n{}",
-                    format_path(actual),
-                    format_path(expected),
+                    "Could not remove actual results file: {}\n{}",
+                    format_path(path),
                     format_error(e),
                 )
             }
+            ExpectError::DeserializeActual(e) => {
+                format!(
+                    "Could not deserialize actual results file.\n{}",
+                    format_error(e.as_ref()),
+                )
+            }
             ExpectError::OpenExpected(e, _) => {
                 format!("Could not open expected results file.\n{}", format_error(e),)
             }
+            ExpectError::WriteExpected(e, path) => {
+                format!(
+                    "Could not write expected results file: {}\n{}",
+                    format_path(path),
+                    format_error(e),
+                )
+            }
             ExpectError::ReadExpected(e) => {
                 format!("Could not read expected results file.\n{}", format_error(e),)
             }
@@ -67,6 +90,29 @@ impl Display for ExpectError {
                     format_error(e.as_ref()),
                 )
             }
+            ExpectError::RunCommand(e, args) => {
+                format!("Could not run command: {args}\n{}", format_error(e))
+            }
+            ExpectError::ReadDirectory(e, path) => {
+                format!(
+                    "Could not read directory: {}\n{}",
+                    format_path(path),
+                    format_error(e),
+                )
+            }
+            ExpectError::ReadFile(e, path) => {
+                format!(
+                    "Could not read file: {}\n{}",
+                    format_path(path),
+                    format_error(e),
+                )
+            }
+            ExpectError::NoFixtures(pattern) => {
+                format!("No fixtures match the pattern: {}", format_path(pattern))
+            }
+            ExpectError::ExpectedNotFound(path) => {
+                format!("Expected results file not found:\n{}", format_path(path))
+            }
         };
         write!(f, "{} to run test. {}", "Failed".bold(), message)
     }
@@ -1,65 +1,530 @@
 use crate::diff::Diff;
 use crate::prelude::*;
-use std::fmt::Debug;
-use std::panic::Location;
+#[cfg(feature = "serde")]
+use std::error::Error;
+use std::fmt::Display;
+use std::panic::{Location, UnwindSafe};
+use std::process::Command;
+use std::sync::Arc;
 
 /// Compare tests results against expected values.
+///
+/// Clones share the mismatches recorded in soft mode.
+///
+/// Snapshots are named after the test, so comparisons with the same extension from several
+/// threads share one snapshot. Use [`Expect::case`] to give each thread its own snapshot.
+#[derive(Clone)]
 pub struct Expect {
     pub(crate) test: TestContext,
+    pub(crate) settings: Settings,
+    /// Paths of the mismatched snapshots recorded in soft mode.
+    mismatches: Arc<Mismatches>,
 }
 
 impl Expect {
     /// Create a new [`Expect`].
+    ///
+    /// Snapshots are placed relative to the file of the caller.
+    ///
+    /// To create an [`Expect`] in a shared helper, mark the helper with `#[track_caller]` so
+    /// the location of the calling test is passed through, or use [`Expect::from_location`].
     #[track_caller]
     #[must_use]
     pub fn new() -> Self {
-        let location = Location::caller();
+        Self::from_location(Location::caller())
+    }
+
+    /// Create a new [`Expect`] with snapshots placed relative to the file of the location.
+    ///
+    /// Example: `Expect::from_location(Location::caller())`
+    #[must_use]
+    pub fn from_location(location: &'static Location<'static>) -> Self {
+        Self::from_settings(location, Settings::load())
+    }
+
+    /// Create an [`ExpectBuilder`] to configure an [`Expect`].
+    ///
+    /// Snapshots are placed relative to the file of the caller.
+    #[track_caller]
+    pub fn builder() -> ExpectBuilder {
+        ExpectBuilder::new(Location::caller())
+    }
+
+    /// Create a new [`Expect`] with the [`Settings`].
+    pub(crate) fn from_settings(location: &'static Location<'static>, settings: Settings) -> Self {
         Self {
             test: TestContext::new(location),
+            settings,
+            mismatches: Arc::default(),
+        }
+    }
+
+    /// Create an [`Expect`] for a labelled case of the current test.
+    ///
+    /// Snapshots of each case are grouped in a directory for the test.
+    ///
+    /// Characters other than letters, digits, `-` and `_` are replaced with `_`.
+    ///
+    /// The label is nested under an existing case, such as one detected from `rstest`.
+    ///
+    /// Example: `test_name/case_1/label`
+    #[must_use]
+    pub fn case(&self, label: &str) -> Self {
+        let label: String = label
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let mut test = self.test.clone();
+        test.case = Some(match &self.test.case {
+            Some(case) => format!("{case}/{label}"),
+            None => label,
+        });
+        Self {
+            test,
+            settings: self.settings.clone(),
+            mismatches: self.mismatches.clone(),
         }
     }
 
     /// Compare a string with the expected value.
-    pub fn string(&mut self, actual: &str, extension: &str) -> Result<bool, ExpectError> {
+    ///
+    /// The string is scrubbed before it is compared.
+    pub fn string(&self, actual: &str, extension: &str) -> Result<bool, ExpectError> {
+        let actual = self.scrub(actual);
+        self.verify_dirs()?;
+        let expected = self.read_expected_text(&actual, extension)?;
+        let is_success = self.get_diff().string(&actual, &expected);
+        self.check(is_success, actual.as_bytes(), extension)
+    }
+
+    /// Run the command and compare the output with the expected value.
+    ///
+    /// The arguments, exit status, stdout and stderr are compared as separate sections.
+    pub fn command(&self, command: &mut Command) -> Result<bool, ExpectError> {
+        let mut snapshot = CommandSnapshot::run(command)?;
+        for value in [
+            &mut snapshot.args,
+            &mut snapshot.status,
+            &mut snapshot.stdout,
+            &mut snapshot.stderr,
+        ] {
+            *value = self.scrub(value);
+        }
+        let actual = snapshot.to_string();
+        self.verify_dirs()?;
+        let expected = self.read_expected_text(&actual, COMMAND_EXT)?;
+        let is_success = self
+            .get_diff()
+            .sections(&parse_sections(&actual), &parse_sections(&expected));
+        self.check(is_success, actual.as_bytes(), COMMAND_EXT)
+    }
+
+    /// Compare the files in a directory with the expected value.
+    ///
+    /// The relative path, size and text contents or binary hash of each file are compared.
+    /// Each file is scrubbed before its size is counted.
+    pub fn directory<P: AsRef<Path>>(&self, path: P) -> Result<bool, ExpectError> {
+        let actual = get_manifest(path.as_ref(), |text| self.scrub(text))?;
         self.verify_dirs()?;
-        self.write_actual_text(actual, extension)?;
-        let expected = self.read_expected_text(extension)?;
-        Ok(Diff::string(actual, &expected))
+        let expected = self.read_expected_text(&actual, DIRECTORY_EXT)?;
+        let is_success = self
+            .get_diff()
+            .sections(&parse_sections(&actual), &parse_sections(&expected));
+        self.check(is_success, actual.as_bytes(), DIRECTORY_EXT)
+    }
+
+    /// Compare each file in a directory with its own expected file.
+    ///
+    /// The expected files mirror the directory in a folder for the test.
+    /// Modified and removed files are reported, new files are created like any other snapshot.
+    /// Symbolic links are skipped.
+    ///
+    /// Example: `src/path/to/module/.expect/file/test_name/nested/file.expect.rs`
+    pub fn directory_files<P: AsRef<Path>>(&self, path: P) -> Result<bool, ExpectError> {
+        let mut failures = Vec::new();
+        let mut expected_paths = Vec::new();
+        for (relative, path) in get_files(path.as_ref())? {
+            if path.is_symlink() {
+                continue;
+            }
+            let file = self.for_directory_file(&relative);
+            let expected = file.get_expected_path(&get_file_extension(&path));
+            if !file.file(&path)? {
+                println!("Changed: {relative}");
+                self.record_mismatch(expected.clone());
+                failures.push(relative);
+            }
+            expected_paths.push(expected);
+        }
+        let files_dir = self.get_files_dir();
+        let marker = format!(".{}.", self.settings.expected_ext);
+        if files_dir.is_dir() {
+            for (relative, path) in get_files(&files_dir)? {
+                if relative.contains(&marker) && !expected_paths.contains(&path) {
+                    println!("Removed: {relative}");
+                    self.record_mismatch(path);
+                    failures.push(relative);
+                }
+            }
+        }
+        if !failures.is_empty() {
+            println!("{} of the files failed:", failures.len());
+            for relative in &failures {
+                println!("- {relative}");
+            }
+        }
+        Ok(failures.is_empty())
+    }
+
+    /// Compare an existing file with the expected value.
+    ///
+    /// The file is copied to the actual results keeping its extension.
+    /// A text file is scrubbed before it is compared.
+    pub fn file<P: AsRef<Path>>(&self, path: P) -> Result<bool, ExpectError> {
+        let path = path.as_ref();
+        let extension = get_file_extension(path);
+        self.verify_dirs()?;
+        let actual = read(path).map_err(|e| ExpectError::ReadFile(e, path.to_path_buf()))?;
+        let actual = self.scrub_bytes(actual);
+        let expected = self.read_expected_bytes(&actual, &extension)?;
+        let is_success = self.get_diff().file(&actual, &expected);
+        self.check(is_success, &actual, &extension)
+    }
+
+    /// Compare the output of the closure for each fixture file with the expected value.
+    ///
+    /// Each fixture has its own snapshot named after the test and the fixture file.
+    /// Every fixture is compared before the failures are reported.
+    pub fn each_fixture<F: FnMut(&str) -> String>(
+        &self,
+        pattern: &str,
+        mut f: F,
+    ) -> Result<bool, ExpectError> {
+        let mut failures = Vec::new();
+        for path in find_fixtures(pattern)? {
+            let input =
+                read_to_string(&path).map_err(|e| ExpectError::ReadFile(e, path.clone()))?;
+            let actual = f(&input);
+            let fixture = self.for_fixture(&path);
+            if !fixture.string(&actual, TEXT_EXT)? {
+                println!("Fixture failed: {}", path.display());
+                self.record_mismatch(fixture.get_expected_path(TEXT_EXT));
+                failures.push(path);
+            }
+        }
+        if !failures.is_empty() {
+            println!("{} of the fixtures failed:", failures.len());
+            for path in &failures {
+                println!("- {}", path.display());
+            }
+        }
+        Ok(failures.is_empty())
+    }
+
+    /// Compare raw bytes with the expected value.
+    ///
+    /// Bytes that are text are scrubbed before they are compared.
+    pub fn bytes(&self, actual: &[u8], extension: &str) -> Result<bool, ExpectError> {
+        let actual = self.scrub_bytes(actual.to_vec());
+        self.verify_dirs()?;
+        let expected = self.read_expected_bytes(&actual, extension)?;
+        let is_success = self.get_diff().bytes(&actual, &expected);
+        self.check(is_success, &actual, extension)
+    }
+
+    /// Compare the panic of a closure with the expected value.
+    ///
+    /// The message and location of the panic are captured.
+    /// If the closure does not panic it is a mismatch.
+    pub fn panics<F: FnOnce() -> R + UnwindSafe, R>(&self, f: F) -> Result<bool, ExpectError> {
+        let Some(actual) = capture_panic(f) else {
+            println!("{}", "Expected the closure to panic".red());
+            self.record_mismatch(self.get_expected_path(PANIC_EXT));
+            return Ok(false);
+        };
+        self.string(&actual.to_string(), PANIC_EXT)
+    }
+
+    /// Compare the pretty [`Debug`] output with the expected value.
+    ///
+    /// Pointer addresses are normalized and the entries of every map and set, such as a
+    /// `HashMap` or `HashSet`, are sorted by their `Debug` output.
+    pub fn debug<T: Debug + ?Sized>(&self, actual: &T) -> Result<bool, ExpectError> {
+        self.render(
+            actual,
+            |actual| normalize_debug(&format!("{actual:#?}")),
+            DEBUG_EXT,
+        )
+    }
+
+    /// Compare the [`Display`] output with the expected value.
+    pub fn display<T: Display + ?Sized>(&self, actual: &T) -> Result<bool, ExpectError> {
+        self.render(actual, ToString::to_string, TEXT_EXT)
+    }
+
+    /// Compare a custom rendering with the expected value.
+    pub fn render<T: ?Sized, F: FnOnce(&T) -> String>(
+        &self,
+        actual: &T,
+        render: F,
+        extension: &str,
+    ) -> Result<bool, ExpectError> {
+        self.string(&render(actual), extension)
     }
 
     /// Compare against the expected value.
+    ///
+    /// If a float precision or scrubbers are set the value is compared in serialized form.
+    #[cfg(feature = "serde")]
     pub fn value<T: Debug + DeserializeOwned + PartialEq + Serialize>(
-        &mut self,
+        &self,
         actual: &T,
     ) -> Result<bool, ExpectError> {
+        if self.settings.should_compare_serialized() {
+            return self.value_serialized(actual);
+        }
         self.verify_dirs()?;
-        self.write_actual_serialized(&actual)?;
-        let expected: T = self.read_expected_serialized()?;
-        Ok(Diff::value(actual, &expected))
+        let bytes = self.serialize_actual(&actual)?;
+        let expected: T = self.read_expected_serialized(&bytes)?;
+        let serializer = self.get_serializer();
+        let is_success = self.get_diff().value(&serializer, actual, &expected);
+        self.check(is_success, &bytes, serializer.get_extension())
     }
 
     /// Compare against the expected values.
+    ///
+    /// If a float precision or scrubbers are set the values are compared in serialized form.
+    #[cfg(feature = "serde")]
     pub fn values<T: Debug + DeserializeOwned + PartialEq + Serialize>(
-        &mut self,
+        &self,
         actual: &[T],
     ) -> Result<bool, ExpectError> {
         self.verify_dirs()?;
-        self.write_actual_serialized(&actual)?;
-        let expected: Vec<T> = self.read_expected_serialized()?;
-        Ok(Diff::values(actual, &expected))
+        let bytes = self.serialize_actual(&actual)?;
+        if self.settings.should_compare_serialized() {
+            let mut actual: Vec<ValueTree> = self.deserialize_actual(&bytes)?;
+            let mut expected: Vec<ValueTree> = self.read_expected_serialized(&bytes)?;
+            if let Some(precision) = self.settings.float_precision {
+                for tree in actual.iter_mut().chain(expected.iter_mut()) {
+                    tree.round_floats(precision);
+                }
+            }
+            let serializer = self.get_serializer();
+            let is_success = self.get_diff().values(&serializer, &actual, &expected);
+            return self.check(is_success, &bytes, serializer.get_extension());
+        }
+        let expected: Vec<T> = self.read_expected_serialized(&bytes)?;
+        let serializer = self.get_serializer();
+        let is_success = self.get_diff().values(&serializer, actual, &expected);
+        self.check(is_success, &bytes, serializer.get_extension())
+    }
+
+    /// Compare against the expected value in serialized form.
+    ///
+    /// Unlike [`Expect::value`] the value is not deserialized so only [`Serialize`] is required.
+    #[cfg(feature = "serde")]
+    pub fn value_serialized<T: Serialize>(&self, actual: &T) -> Result<bool, ExpectError> {
+        self.verify_dirs()?;
+        let bytes = self.serialize_actual(&actual)?;
+        let mut actual: ValueTree = self.deserialize_actual(&bytes)?;
+        let mut expected: ValueTree = self.read_expected_serialized(&bytes)?;
+        if let Some(precision) = self.settings.float_precision {
+            actual.round_floats(precision);
+            expected.round_floats(precision);
+        }
+        let serializer = self.get_serializer();
+        let is_success = self.get_diff().value(&serializer, &actual, &expected);
+        self.check(is_success, &bytes, serializer.get_extension())
+    }
+
+    /// Compare a [`Result`] against the expected value in serialized form.
+    ///
+    /// An `Err` is recorded as the [`Display`] message of each error in the [`Error::source`] chain.
+    #[cfg(feature = "serde")]
+    pub fn result<T: Serialize, E: Error>(
+        &self,
+        actual: &Result<T, E>,
+    ) -> Result<bool, ExpectError> {
+        self.value_serialized(&ResultSnapshot::from(actual))
+    }
+
+    /// Panic with a report of the mismatches recorded in soft mode.
+    pub fn finish(self) {
+        self.mismatches.report();
+    }
+
+    /// Create an [`Expect`] for a fixture of the current test.
+    ///
+    /// The extension of the fixture is kept so `a.json` and `a.yaml` have separate snapshots.
+    ///
+    /// Example: `test_name.fixture_name.json`
+    fn for_fixture(&self, path: &Path) -> Self {
+        let fixture = path
+            .file_name()
+            .expect("Fixture should have a file name")
+            .to_string_lossy();
+        let mut test = self.test.clone();
+        test.name = format!("{}.{fixture}", test.name);
+        let mut settings = self.settings.clone();
+        settings.soft = false;
+        Self {
+            test,
+            settings,
+            mismatches: Arc::default(),
+        }
+    }
+
+    /// Create an [`Expect`] for a file of [`Expect::directory_files`].
+    ///
+    /// Example: `test_name/nested/file`
+    fn for_directory_file(&self, relative: &str) -> Self {
+        let relative = Path::new(relative).with_extension("");
+        let relative = relative.to_string_lossy();
+        let mut test = self.test.clone();
+        test.case = Some(match &self.test.case {
+            Some(case) => format!("{case}/{relative}"),
+            None => relative.into_owned(),
+        });
+        let mut settings = self.settings.clone();
+        settings.soft = false;
+        Self {
+            test,
+            settings,
+            mismatches: Arc::default(),
+        }
+    }
+
+    /// Write the actual results file and record the mismatch if in soft mode.
+    ///
+    /// If only mismatches are kept the actual results of a match are not written and any
+    /// previous actual results file is removed.
+    fn check(&self, is_success: bool, actual: &[u8], extension: &str) -> Result<bool, ExpectError> {
+        if !is_success {
+            self.write_actual_bytes(actual, extension)?;
+            self.record_mismatch(self.get_expected_path(extension));
+        } else if self.settings.actual_on_mismatch {
+            self.remove_actual(extension)?;
+        } else {
+            self.write_actual_bytes(actual, extension)?;
+        }
+        Ok(is_success)
+    }
+
+    /// Record the path of a mismatched snapshot if in soft mode.
+    fn record_mismatch(&self, path: PathBuf) {
+        if self.settings.soft {
+            self.mismatches.push(path);
+        }
+    }
+
+    /// Apply each [`Scrubber`] to the text.
+    pub(crate) fn scrub(&self, text: &str) -> String {
+        self.settings
+            .scrubbers
+            .iter()
+            .fold(text.to_owned(), |text, scrubber| scrubber.scrub(&text))
+    }
+
+    /// Apply each [`Scrubber`] to the bytes if they are text.
+    fn scrub_bytes(&self, bytes: Vec<u8>) -> Vec<u8> {
+        match get_text(&bytes) {
+            Some(text) if !self.settings.scrubbers.is_empty() => self.scrub(text).into_bytes(),
+            _ => bytes,
+        }
+    }
+
+    /// Get the [`Diff`] with the [`DiffOptions`].
+    fn get_diff(&self) -> Diff {
+        Diff::new(self.settings.diff.clone())
+    }
+
+    /// Get the [`Serializer`] for the selected [`Format`].
+    #[cfg(feature = "serde")]
+    pub(crate) fn get_serializer(&self) -> FormatSerializer {
+        FormatSerializer::from(&self.settings)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use std::env::{current_dir, temp_dir};
+    use std::panic::AssertUnwindSafe;
+    #[cfg(feature = "serde")]
+    use std::slice;
+    use std::thread;
+
+    #[test]
+    fn new_track_caller() {
+        // Arrange
+        // Act
+        let expect = sample_helper();
+        // Assert
+        assert_eq!(expect.test.file, PathBuf::from("src/expect.rs"));
+        assert_eq!(expect.test.name, "new_track_caller");
+    }
+
+    #[test]
+    fn from_location() {
+        // Arrange
+        let location = Location::caller();
+        // Act
+        let expect = sample_helper_with_location(location);
+        // Assert
+        assert_eq!(expect.test.file, PathBuf::from("src/expect.rs"));
+        assert_eq!(expect.test.line, location.line());
+    }
+
+    #[test]
+    fn clone_send_sync() {
+        // Arrange
+        fn assert_clone_send_sync<T: Clone + Send + Sync>() {}
+        // Act
+        // Assert
+        assert_clone_send_sync::<Expect>();
+    }
+
+    #[test]
+    fn threads() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::new();
+        // Act
+        let results: Vec<Result<bool, ExpectError>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let expect = &expect;
+                    scope.spawn(move || {
+                        expect
+                            .case(&format!("worker_{i}"))
+                            .string(&format!("Worker {i}"), TEXT_EXT)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Thread should not panic"))
+                .collect()
+        });
+        // Assert
+        for result in results {
+            assert!(result?, "Valid");
+        }
+        Ok(())
+    }
 
     #[test]
     fn string() -> Result<(), ExpectError> {
         // Arrange
         let valid = "Hello, world!";
         let invalid = "Oh, no!";
-        let mut expect = Expect::new();
+        let expect = Expect::new();
         // Act
         // Assert
         assert!(expect.string(valid, TEXT_EXT)?, "Valid");
@@ -68,6 +533,405 @@ mod tests {
     }
 
     #[test]
+    fn command() -> Result<(), ExpectError> {
+        // Arrange
+        let mut valid = Command::new("sh");
+        valid.args(["-c", "pwd; echo Oh, no! >&2; exit 1"]);
+        let mut invalid = Command::new("sh");
+        invalid.args(["-c", "pwd; exit 1"]);
+        let dir = current_dir().expect("Should have a current directory");
+        let expect = Expect::builder()
+            .with_scrubber(Scrubber::replace(&dir.to_string_lossy(), "[CWD]"))
+            .build();
+        // Act
+        // Assert
+        assert!(expect.command(&mut valid)?, "Valid");
+        assert!(!expect.command(&mut invalid)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    fn scrubbers() -> Result<(), ExpectError> {
+        // Arrange
+        let path = temp_dir().join("expect_scrubbers.txt");
+        write(&path, "a secret\n").expect("Should be able to write file");
+        let expect = Expect::builder()
+            .with_scrubber(Scrubber::replace("secret", "[REDACTED]"))
+            .build();
+        // Act
+        // Assert
+        assert!(
+            expect.case("string").string("a secret", TEXT_EXT)?,
+            "String"
+        );
+        assert!(expect.case("display").display(&"a secret")?, "Display");
+        assert!(expect.case("debug").debug(&"a secret")?, "Debug");
+        assert!(expect.case("file").file(&path)?, "File");
+        assert!(expect.case("bytes").bytes(b"a secret", TEXT_EXT)?, "Bytes");
+        #[cfg(feature = "serde")]
+        assert!(
+            expect.case("value").value(&vec!["a secret".to_owned()])?,
+            "Value"
+        );
+        let expected = read_to_string(expect.case("string").get_expected_path(TEXT_EXT))
+            .expect("Should be able to read file");
+        assert_eq!(expected, "a [REDACTED]");
+        Ok(())
+    }
+
+    #[test]
+    fn command_scrub_sections() -> Result<(), ExpectError> {
+        // Arrange
+        let mut command = Command::new("printf");
+        command.arg(r"a\nsecret\nb\n");
+        let expect = Expect::builder()
+            .with_scrubber(Scrubber::replace("secret\n", ""))
+            .build();
+        // Act
+        let result = expect.command(&mut command)?;
+        // Assert
+        assert!(result);
+        let expected = read_to_string(expect.get_expected_path(COMMAND_EXT))
+            .expect("Should be able to read file");
+        assert!(
+            expected.contains("--- stdout [2 lines] ---\na\nb\n"),
+            "{expected}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn directory() -> Result<(), ExpectError> {
+        // Arrange
+        let root = temp_dir().join("expect_directory");
+        if root.exists() {
+            remove_dir_all(&root).expect("Should be able to remove directory");
+        }
+        create_dir_all(root.join("src")).expect("Should be able to create directory");
+        write(root.join("README.md"), "# Hello\n").expect("Should be able to write file");
+        write(root.join("src/lib.rs"), "pub fn hello() {}\n")
+            .expect("Should be able to write file");
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.directory(&root)?, "Valid");
+        write(root.join("src/main.rs"), "fn main() {}\n").expect("Should be able to write file");
+        write(root.join("src/lib.rs"), "").expect("Should be able to write file");
+        remove_file(root.join("README.md")).expect("Should be able to remove file");
+        assert!(!expect.directory(&root)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    fn directory_files() -> Result<(), ExpectError> {
+        // Arrange
+        let root = temp_dir().join("expect_directory_files");
+        if root.exists() {
+            remove_dir_all(&root).expect("Should be able to remove directory");
+        }
+        create_dir_all(root.join("src")).expect("Should be able to create directory");
+        write(root.join("README.md"), "# Hello\n").expect("Should be able to write file");
+        write(root.join("src/lib.rs"), "pub fn hello() {}\n")
+            .expect("Should be able to write file");
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.directory_files(&root)?, "Valid");
+        assert_eq!(
+            expect.get_files_dir(),
+            PathBuf::from("src/.expect/expect/directory_files")
+        );
+        assert!(
+            expect.get_files_dir().join("src/lib.expect.rs").is_file(),
+            "Mirrored"
+        );
+        write(root.join("src/lib.rs"), "").expect("Should be able to write file");
+        assert!(!expect.directory_files(&root)?, "Modified");
+        write(root.join("src/lib.rs"), "pub fn hello() {}\n")
+            .expect("Should be able to write file");
+        remove_file(root.join("README.md")).expect("Should be able to remove file");
+        assert!(!expect.directory_files(&root)?, "Removed");
+        Ok(())
+    }
+
+    #[test]
+    fn file() -> Result<(), ExpectError> {
+        // Arrange
+        let path = temp_dir().join("expect_file_report.csv");
+        let expect = Expect::new();
+        // Act
+        // Assert
+        write(&path, "name,value\na,1\n").expect("Should be able to write file");
+        assert!(expect.file(&path)?, "Valid");
+        write(&path, "name,value\na,2\n").expect("Should be able to write file");
+        assert!(!expect.file(&path)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    fn each_fixture() -> Result<(), ExpectError> {
+        // Arrange
+        let valid = |input: &str| input.to_uppercase();
+        let invalid = |input: &str| input.to_lowercase();
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(
+            expect.each_fixture("src/samples/fixtures/*.input", valid)?,
+            "Valid"
+        );
+        assert!(
+            !expect.each_fixture("src/samples/fixtures/*.input", invalid)?,
+            "Invalid"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn for_fixture_extension() {
+        // Arrange
+        let expect = Expect::new();
+        // Act
+        let json = expect.for_fixture(Path::new("fixtures/a.json"));
+        let yaml = expect.for_fixture(Path::new("fixtures/a.yaml"));
+        // Assert
+        assert_eq!(
+            json.get_expected_path(TEXT_EXT),
+            PathBuf::from("src/.expect/expect/for_fixture_extension.a.json.expect.txt")
+        );
+        assert_ne!(
+            json.get_expected_path(TEXT_EXT),
+            yaml.get_expected_path(TEXT_EXT)
+        );
+    }
+
+    #[test]
+    fn case() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.case("english").string("Hello", TEXT_EXT)?, "Valid");
+        assert!(expect.case("french").string("Bonjour", TEXT_EXT)?, "Valid");
+        assert!(
+            !expect.case("english").string("Bonjour", TEXT_EXT)?,
+            "Invalid"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn case_nested_in_detected_case() {
+        // Arrange
+        let mut expect = Expect::new();
+        expect.test.case = Some("case_1_english".to_owned());
+        let other = Expect::new().case("case_2_french");
+        // Act
+        let result = expect.case("greeting");
+        let other = other.case("greeting");
+        // Assert
+        assert_eq!(
+            result.get_expected_path(TEXT_EXT),
+            PathBuf::from(format!(
+                "src/.expect/expect/case_nested_in_detected_case/case_1_english/greeting.{EXPECT_EXT}.{TEXT_EXT}"
+            ))
+        );
+        assert_ne!(
+            result.get_expected_path(TEXT_EXT),
+            other.get_expected_path(TEXT_EXT),
+            "Cases don't collide"
+        );
+    }
+
+    #[test]
+    fn actual_dir() -> Result<(), ExpectError> {
+        // Arrange
+        let dir = temp_dir().join("expect_actual_dir");
+        let expect = Expect::builder().with_actual_dir(&dir).build();
+        // Act
+        let result = expect.string("Hello, world!", TEXT_EXT)?;
+        // Assert
+        assert!(result);
+        let path = expect.get_actual_path(TEXT_EXT);
+        assert!(path.starts_with(&dir), "Actual in directory");
+        assert!(path.is_file(), "Actual in directory");
+        assert!(
+            !Path::new("src/.expect/expect/actual_dir.actual.txt").exists(),
+            "Actual not alongside"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn actual_on_mismatch() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::builder().with_actual_on_mismatch(true).build();
+        // Act
+        // Assert
+        assert!(expect.string("Hello, world!", TEXT_EXT)?, "Valid");
+        assert!(
+            !expect.get_actual_path(TEXT_EXT).exists(),
+            "Actual of a match is removed"
+        );
+        assert!(!expect.string("INVALID", TEXT_EXT)?, "Invalid");
+        assert!(
+            expect.get_actual_path(TEXT_EXT).is_file(),
+            "Actual of a mismatch is kept"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn actual_on_mismatch_not_written() -> Result<(), ExpectError> {
+        // Arrange
+        let root = temp_dir().join("expect_actual_on_mismatch_not_written");
+        if root.exists() {
+            remove_dir_all(&root).expect("Should be able to remove directory");
+        }
+        let expect = Expect::builder()
+            .with_snapshot_root(&root)
+            .with_actual_on_mismatch(true)
+            .build();
+        // Act
+        let result = expect.string("Hello, world!", TEXT_EXT)?;
+        // Assert
+        assert!(result, "Expected created");
+        assert!(
+            expect.get_expected_path(TEXT_EXT).is_file(),
+            "Expected created"
+        );
+        assert!(
+            !expect.get_actual_path(TEXT_EXT).exists(),
+            "Actual of a match is never written"
+        );
+        remove_dir_all(&root).expect("Should be able to remove directory");
+        Ok(())
+    }
+
+    #[test]
+    fn soft() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::builder().with_soft(true).build();
+        // Act
+        let valid = expect.string("Hello, world!", TEXT_EXT)?;
+        let invalid = expect.string("Oh, no!", TEXT_EXT)?;
+        let invalid_again = expect.display(&"Oh, no!")?;
+        let result = capture_panic(AssertUnwindSafe(|| expect.finish()));
+        // Assert
+        assert!(valid, "Valid");
+        assert!(!invalid, "Invalid");
+        assert!(!invalid_again, "Invalid again");
+        let result = result.expect("Should panic");
+        assert!(
+            result.message.starts_with("2 snapshots did not match:"),
+            "{}",
+            result.message
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn soft_drop() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::builder().with_soft(true).build();
+        // Act
+        let valid = expect.string("Hello, world!", TEXT_EXT)?;
+        let invalid = expect.string("Oh, no!", TEXT_EXT)?;
+        let result = capture_panic(AssertUnwindSafe(|| drop(expect)));
+        // Assert
+        assert!(valid, "Valid");
+        assert!(!invalid, "Invalid");
+        assert!(result.is_some(), "Should panic on drop");
+        Ok(())
+    }
+
+    #[test]
+    fn bytes() -> Result<(), ExpectError> {
+        // Arrange
+        let valid = [0x00, 0x01, 0xca, 0xfe, 0xba, 0xbe, 0xff];
+        let invalid = [0x00, 0x01, 0xca, 0xfe, 0xba, 0xbe];
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.bytes(&valid, "bin")?, "Valid");
+        assert!(!expect.bytes(&invalid, "bin")?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    fn panics() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.panics(|| sample_panic("Hello, world!"))?, "Valid");
+        assert!(!expect.panics(|| sample_panic("Oh, no!"))?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    fn panics_without_panic() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::new();
+        // Act
+        let result = expect.panics(|| 1 + 1)?;
+        // Assert
+        assert!(!result, "Mismatch");
+        assert!(
+            !expect.get_expected_path(PANIC_EXT).exists(),
+            "Expected file not written"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn debug() -> Result<(), ExpectError> {
+        // Arrange
+        let valid = HashMap::from([("b", Some(2)), ("a", None), ("c", Some(3))]);
+        let invalid = HashMap::from([("b", Some(2)), ("a", Some(1))]);
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.debug(&valid)?, "Valid");
+        assert!(!expect.debug(&invalid)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    fn display() -> Result<(), ExpectError> {
+        // Arrange
+        let valid = PathBuf::from("path/to/file.txt");
+        let invalid = PathBuf::from("path/to/other.txt");
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.display(&valid.display())?, "Valid");
+        assert!(!expect.display(&invalid.display())?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    fn render() -> Result<(), ExpectError> {
+        // Arrange
+        let render = |values: &[u32]| {
+            values
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let valid: &[u32] = &[1, 2, 3];
+        let invalid: &[u32] = &[1, 2];
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.render(valid, render, "csv")?, "Valid");
+        assert!(!expect.render(invalid, render, "csv")?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
     fn value() -> Result<(), ExpectError> {
         // Arrange
         let valid = SampleStruct::sample();
@@ -75,7 +939,7 @@ mod tests {
             string: "INVALID".to_owned(),
             ..SampleStruct::sample()
         };
-        let mut expect = Expect::new();
+        let expect = Expect::new();
         // Act
         // Assert
         assert!(expect.value(&valid)?, "Valid");
@@ -84,6 +948,8 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "serde")]
+    #[allow(clippy::useless_vec, clippy::cloned_ref_to_slice_refs)]
     fn values() -> Result<(), ExpectError> {
         // Arrange
         let valid = SampleStruct::sample();
@@ -91,7 +957,7 @@ mod tests {
             string: "INVALID".to_owned(),
             ..SampleStruct::sample()
         };
-        let mut expect = Expect::new();
+        let expect = Expect::new();
         // Act
         // Assert
         assert!(expect.values(&vec![valid.clone(), valid.clone()])?, "Valid");
@@ -106,4 +972,153 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn value_float_precision() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::builder().with_float_precision(2).build();
+        // Act
+        // Assert
+        assert!(expect.value(&vec![1.5_f64, 0.1 + 0.2])?, "Valid");
+        assert!(expect.value(&vec![1.501_f64, 0.3])?, "Within precision");
+        assert!(!expect.value(&vec![1.51_f64, 0.3])?, "Invalid");
+        assert!(expect.values(&[1.5_f64, 0.3])?, "Values within precision");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn value_serialized() -> Result<(), ExpectError> {
+        // Arrange
+        #[derive(Serialize)]
+        struct OutputOnly<'a> {
+            name: &'a str,
+            values: Vec<u32>,
+        }
+        let valid = OutputOnly {
+            name: "Hello, world!",
+            values: vec![1, 2, 3],
+        };
+        let invalid = OutputOnly {
+            name: "INVALID",
+            values: vec![1, 2, 3],
+        };
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.value_serialized(&valid)?, "Valid");
+        assert!(!expect.value_serialized(&invalid)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn result_ok() -> Result<(), ExpectError> {
+        // Arrange
+        let valid: Result<_, SampleError> = Ok(SampleStruct::sample());
+        let invalid: Result<SampleStruct, _> = Err(SampleError::sample("File not found"));
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.result(&valid)?, "Valid");
+        assert!(!expect.result(&invalid)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn result_err() -> Result<(), ExpectError> {
+        // Arrange
+        let valid: Result<SampleStruct, _> = Err(SampleError::sample("File not found"));
+        let invalid: Result<SampleStruct, _> = Err(SampleError::sample("Permission denied"));
+        let expect = Expect::new();
+        // Act
+        // Assert
+        assert!(expect.result(&valid)?, "Valid");
+        assert!(!expect.result(&invalid)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn value_yaml_documents() -> Result<(), ExpectError> {
+        // Arrange
+        let valid = SampleStruct::sample();
+        let invalid = SampleStruct {
+            string: "INVALID".to_owned(),
+            ..SampleStruct::sample()
+        };
+        let expect = Expect::builder().with_format(Format::YamlDocuments).build();
+        // Act
+        // Assert
+        assert!(expect.value(&valid)?, "Valid");
+        assert!(!expect.value(&invalid)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn values_yaml_documents() -> Result<(), ExpectError> {
+        // Arrange
+        let valid = SampleStruct::sample();
+        let invalid = SampleStruct {
+            string: "INVALID".to_owned(),
+            ..SampleStruct::sample()
+        };
+        let expect = Expect::builder().with_format(Format::YamlDocuments).build();
+        // Act
+        // Assert
+        assert!(expect.values(&[valid.clone(), valid.clone()])?, "Valid");
+        assert!(
+            !expect.values(slice::from_ref(&valid))?,
+            "Missing on actual"
+        );
+        assert!(
+            !expect.values(&[valid.clone(), invalid.clone()])?,
+            "Invalid"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn value_json_lines() -> Result<(), ExpectError> {
+        // Arrange
+        let valid = SampleStruct::sample();
+        let invalid = SampleStruct {
+            string: "INVALID".to_owned(),
+            ..SampleStruct::sample()
+        };
+        let expect = Expect::builder().with_format(Format::JsonLines).build();
+        // Act
+        // Assert
+        assert!(expect.value(&valid)?, "Valid");
+        assert!(!expect.value(&invalid)?, "Invalid");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn values_json_lines() -> Result<(), ExpectError> {
+        // Arrange
+        let valid = SampleStruct::sample();
+        let invalid = SampleStruct {
+            string: "INVALID".to_owned(),
+            ..SampleStruct::sample()
+        };
+        let expect = Expect::builder().with_format(Format::JsonLines).build();
+        // Act
+        // Assert
+        assert!(expect.values(&[valid.clone(), valid.clone()])?, "Valid");
+        assert!(
+            !expect.values(slice::from_ref(&valid))?,
+            "Missing on actual"
+        );
+        assert!(
+            !expect.values(&[valid.clone(), invalid.clone()])?,
+            "Invalid"
+        );
+        Ok(())
+    }
 }
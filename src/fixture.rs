@@ -0,0 +1,109 @@
+use crate::prelude::*;
+
+/// Find the fixture files matching a pattern.
+///
+/// Only the file name may contain `*` and `?` wildcards.
+///
+/// Example: `tests/fixtures/*.sql`
+pub(crate) fn find_fixtures(pattern: &str) -> Result<Vec<PathBuf>, ExpectError> {
+    let pattern = Path::new(pattern);
+    let dir = match pattern.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_pattern = pattern
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let entries = read_dir(dir).map_err(|e| ExpectError::ReadDirectory(e, dir.to_path_buf()))?;
+    let mut fixtures = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| ExpectError::ReadDirectory(e, dir.to_path_buf()))?
+            .path();
+        let is_match = path
+            .file_name()
+            .is_some_and(|name| is_wildcard_match(&file_pattern, &name.to_string_lossy()));
+        if is_match && path.is_file() {
+            fixtures.push(path);
+        }
+    }
+    if fixtures.is_empty() {
+        return Err(ExpectError::NoFixtures(pattern.to_path_buf()));
+    }
+    fixtures.sort();
+    Ok(fixtures)
+}
+
+/// Does the name match a pattern of `*` and `?` wildcards?
+fn is_wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let mut p = 0;
+    let mut n = 0;
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || Some(c) == name.get(n) => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern.iter().skip(p).all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_fixtures_pattern() -> Result<(), ExpectError> {
+        // Arrange
+        // Act
+        let result = find_fixtures("src/samples/fixtures/*.input")?;
+        // Assert
+        assert_eq!(
+            result,
+            vec![
+                PathBuf::from("src/samples/fixtures/greeting.input"),
+                PathBuf::from("src/samples/fixtures/numbers.input"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_fixtures_none() {
+        // Arrange
+        // Act
+        let result = find_fixtures("src/samples/fixtures/*.missing");
+        // Assert
+        assert!(matches!(result, Err(ExpectError::NoFixtures(_))));
+    }
+
+    #[test]
+    fn is_wildcard_match_patterns() {
+        // Arrange
+        // Act
+        // Assert
+        assert!(is_wildcard_match("*.sql", "query.sql"));
+        assert!(is_wildcard_match("*", "query.sql"));
+        assert!(is_wildcard_match("q?ery.*", "query.sql"));
+        assert!(is_wildcard_match("*.*.sql", "a.b.sql"));
+        assert!(!is_wildcard_match("*.sql", "query.sql.bak"));
+        assert!(!is_wildcard_match("*.sql", "query.txt"));
+    }
+}
@@ -1,5 +1,4 @@
 mod paths;
 mod read;
 mod write;
-#[cfg(test)]
 pub(crate) use paths::*;
@@ -1,22 +1,51 @@
 use crate::prelude::*;
+use std::path::Component;
 
 pub(crate) const EXPECT_DIR: &str = ".expect";
+pub(crate) const SOURCE_DIR: &str = "src";
 pub(crate) const ACTUAL_EXT: &str = "actual";
 pub(crate) const EXPECT_EXT: &str = "expect";
-#[cfg(test)]
+pub(crate) const DEBUG_EXT: &str = "debug.txt";
+pub(crate) const PANIC_EXT: &str = "panic.txt";
+pub(crate) const COMMAND_EXT: &str = "command.txt";
+pub(crate) const DIRECTORY_EXT: &str = "directory.txt";
+pub(crate) const BINARY_EXT: &str = "bin";
 pub(crate) const TEXT_EXT: &str = "txt";
 
+/// Get the extension of a file, or `bin` if it has none.
+pub(crate) fn get_file_extension(path: &Path) -> String {
+    path.extension()
+        .map_or(BINARY_EXT.into(), |extension| extension.to_string_lossy())
+        .into_owned()
+}
+
+/// Get the normal components of a path so it stays inside the directory it is joined to.
+///
+/// Example: `/path/to/snapshots` becomes `path/to/snapshots`
+fn get_relative_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
+
 impl Expect {
     /// Get the expect directory path for the module.
     ///
     /// Example: `src/path/to/module/.expect`
+    ///
+    /// With a snapshot root the source directory is mirrored.
+    ///
+    /// Example: `tests/snapshots/path/to/module`
     fn get_module_dir(&self) -> PathBuf {
         let dir = self
             .test
             .file
             .parent()
             .expect("Test file should have a parent directory");
-        dir.join(EXPECT_DIR)
+        match &self.settings.snapshot_root {
+            Some(root) => root.join(dir.strip_prefix(SOURCE_DIR).unwrap_or(dir)),
+            None => dir.join(&self.settings.snapshot_dir),
+        }
     }
 
     /// Get the expect directory path for the current test.
@@ -31,31 +60,90 @@ impl Expect {
         self.get_module_dir().join(file_stem)
     }
 
-    /// Get the results directory path for the current test.
+    /// Get the directory path of the results files.
+    ///
+    /// Cases are grouped in a directory for the test.
+    ///
+    /// Example: `src/path/to/module/.expect/file/test_name`
+    fn get_results_dir(&self) -> PathBuf {
+        match self.test.case {
+            Some(_) => self.get_test_dir().join(&self.test.name),
+            None => self.get_test_dir(),
+        }
+    }
+
+    /// Get the directory mirroring the files of [`Expect::directory_files`].
+    ///
+    /// Example: `src/path/to/module/.expect/file/test_name`
+    pub(crate) fn get_files_dir(&self) -> PathBuf {
+        let dir = self.get_test_dir().join(&self.test.name);
+        match &self.test.case {
+            Some(case) => dir.join(case),
+            None => dir,
+        }
+    }
+
+    /// Get the directory containing the results files for the current test.
+    ///
+    /// The label of a file in [`Expect::directory_files`] includes its subdirectories.
+    fn get_test_file_dir(&self) -> PathBuf {
+        self.get_test_file(EXPECT_EXT)
+            .parent()
+            .expect("Results file should have a parent directory")
+            .to_path_buf()
+    }
+
+    /// Get the results file path for the current test.
     fn get_test_file(&self, extension: &str) -> PathBuf {
-        self.get_test_dir()
-            .join(format!("{}.{extension}", self.test.name))
+        let name = self.test.case.as_ref().unwrap_or(&self.test.name);
+        self.get_results_dir().join(format!("{name}.{extension}"))
     }
 
     /// Get the path of the actual test results.
-    pub(super) fn get_actual_path(&self, extension: &str) -> PathBuf {
-        self.get_test_file(&format!("{ACTUAL_EXT}.{extension}"))
+    ///
+    /// With an actual directory the snapshot layout is mirrored.
+    ///
+    /// Example: `target/expect/src/path/to/module/.expect/file/test_name.actual.txt`
+    pub(crate) fn get_actual_path(&self, extension: &str) -> PathBuf {
+        let path = self.get_test_file(&format!("{}.{extension}", self.settings.actual_ext));
+        match &self.settings.actual_dir {
+            Some(dir) => dir.join(get_relative_path(&path)),
+            None => path,
+        }
     }
 
     /// Get the path of the expected test results.
-    pub(super) fn get_expected_path(&self, extension: &str) -> PathBuf {
-        self.get_test_file(&format!("{EXPECT_EXT}.{extension}"))
+    pub(crate) fn get_expected_path(&self, extension: &str) -> PathBuf {
+        self.get_test_file(&format!("{}.{extension}", self.settings.expected_ext))
     }
 
-    pub(crate) fn verify_dirs(&mut self) -> Result<(), ExpectError> {
-        let module_dir = self.get_module_dir();
-        if !module_dir.is_dir() {
-            return Err(ExpectError::ExpectDirNotFound(module_dir));
+    /// Verify the snapshot directories exist.
+    ///
+    /// The snapshot root, or the directory of the module, is only created if enabled, otherwise
+    /// it must already exist.
+    pub(crate) fn verify_dirs(&self) -> Result<(), ExpectError> {
+        let base_dir = match &self.settings.snapshot_root {
+            Some(root) => root.clone(),
+            None => self.get_module_dir(),
+        };
+        if !base_dir.is_dir() {
+            if !self.settings.should_create_dirs() {
+                return Err(ExpectError::ExpectDirNotFound(base_dir));
+            }
+            println!("Creating expect directory: {}", base_dir.display());
+            create_dir_all(&base_dir).map_err(|e| ExpectError::CreateSubDir(e, base_dir))?;
         }
-        let test_dir = self.get_test_dir();
+        let test_dir = self.get_test_file_dir();
         if !test_dir.is_dir() {
             create_dir_all(&test_dir).map_err(|e| ExpectError::CreateSubDir(e, test_dir))?;
         }
+        if let Some(dir) = &self.settings.actual_dir {
+            let actual_dir = dir.join(get_relative_path(&self.get_test_file_dir()));
+            if !actual_dir.is_dir() {
+                create_dir_all(&actual_dir)
+                    .map_err(|e| ExpectError::CreateSubDir(e, actual_dir))?;
+            }
+        }
         Ok(())
     }
 }
@@ -63,6 +151,7 @@ impl Expect {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::env::temp_dir;
 
     #[test]
     fn get_expect_dir() {
@@ -74,6 +163,35 @@ mod tests {
         assert_eq!(path, PathBuf::from(format!("src/fs/{EXPECT_DIR}")));
     }
 
+    #[test]
+    fn get_expect_dir_with_root() {
+        // Arrange
+        let expect = Expect::builder()
+            .with_snapshot_root("tests/snapshots")
+            .build();
+        // Act
+        let path = expect.get_module_dir();
+        // Assert
+        assert_eq!(path, PathBuf::from("tests/snapshots/fs"));
+    }
+
+    #[test]
+    fn get_expected_path_with_root() {
+        // Arrange
+        let expect = Expect::builder()
+            .with_snapshot_root("tests/snapshots")
+            .build();
+        // Act
+        let path = expect.get_expected_path(TEXT_EXT);
+        // Assert
+        assert_eq!(
+            path,
+            PathBuf::from(format!(
+                "tests/snapshots/fs/paths/get_expected_path_with_root.{EXPECT_EXT}.{TEXT_EXT}"
+            ))
+        );
+    }
+
     #[test]
     fn get_results_dir_for_test() {
         // Arrange
@@ -84,6 +202,21 @@ mod tests {
         assert_eq!(path, PathBuf::from(format!("src/fs/{EXPECT_DIR}/paths")));
     }
 
+    #[test]
+    fn get_expected_path_for_case() {
+        // Arrange
+        let expect = Expect::new().case("Hello, world!");
+        // Act
+        let path = expect.get_expected_path(TEXT_EXT);
+        // Assert
+        assert_eq!(
+            path,
+            PathBuf::from(format!(
+                "src/fs/{EXPECT_DIR}/paths/get_expected_path_for_case/Hello__world_.{EXPECT_EXT}.{TEXT_EXT}"
+            ))
+        );
+    }
+
     #[test]
     fn get_actual_path() {
         // Arrange
@@ -99,6 +232,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_dirs_create() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::builder()
+            .with_snapshot_dir(".expect_create")
+            .with_create_dirs(true)
+            .build();
+        let module_dir = expect.get_module_dir();
+        if module_dir.exists() {
+            remove_dir_all(&module_dir).expect("Should be able to remove directory");
+        }
+        // Act
+        expect.verify_dirs()?;
+        // Assert
+        assert!(expect.get_results_dir().is_dir());
+        remove_dir_all(&module_dir).expect("Should be able to remove directory");
+        Ok(())
+    }
+
+    #[test]
+    fn verify_dirs_strict() {
+        // Arrange
+        let expect = Expect::builder()
+            .with_snapshot_dir(".expect_strict")
+            .with_create_dirs(false)
+            .build();
+        // Act
+        let result = expect.verify_dirs();
+        // Assert
+        assert!(matches!(result, Err(ExpectError::ExpectDirNotFound(_))));
+    }
+
+    #[test]
+    fn get_actual_path_with_dir() {
+        // Arrange
+        let expect = Expect::builder().with_actual_dir("target/expect").build();
+        // Act
+        let path = expect.get_actual_path(TEXT_EXT);
+        // Assert
+        assert_eq!(
+            path,
+            PathBuf::from(format!(
+                "target/expect/src/fs/{EXPECT_DIR}/paths/get_actual_path_with_dir.{ACTUAL_EXT}.{TEXT_EXT}"
+            ))
+        );
+    }
+
+    #[test]
+    fn get_actual_path_with_dir_and_absolute_root() {
+        // Arrange
+        let root = temp_dir().join("expect_absolute_root");
+        let expect = Expect::builder()
+            .with_snapshot_root(&root)
+            .with_actual_dir("target/expect")
+            .build();
+        // Act
+        let path = expect.get_actual_path(TEXT_EXT);
+        // Assert
+        assert!(path.starts_with("target/expect"), "{}", path.display());
+        assert!(
+            path.ends_with(format!(
+                "expect_absolute_root/fs/paths/get_actual_path_with_dir_and_absolute_root.{ACTUAL_EXT}.{TEXT_EXT}"
+            )),
+            "{}",
+            path.display()
+        );
+    }
+
     #[test]
     fn get_expected_path() {
         // Arrange
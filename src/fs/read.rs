@@ -2,8 +2,14 @@ use crate::prelude::*;
 
 impl Expect {
     /// Read the expected results file as text to a string.
-    pub(crate) fn read_expected_text(&mut self, extension: &str) -> Result<String, ExpectError> {
-        let mut reader = self.get_expected_reader(extension)?;
+    ///
+    /// The actual results create or update the expected results file per the [`UpdateMode`].
+    pub(crate) fn read_expected_text(
+        &self,
+        actual: &str,
+        extension: &str,
+    ) -> Result<String, ExpectError> {
+        let mut reader = self.get_expected_reader(actual.as_bytes(), extension)?;
         let mut text = String::new();
         reader
             .read_to_string(&mut text)
@@ -11,26 +17,75 @@ impl Expect {
         Ok(text)
     }
 
+    /// Read the expected results file as raw bytes.
+    pub(crate) fn read_expected_bytes(
+        &self,
+        actual: &[u8],
+        extension: &str,
+    ) -> Result<Vec<u8>, ExpectError> {
+        let mut reader = self.get_expected_reader(actual, extension)?;
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(ExpectError::ReadExpected)?;
+        Ok(bytes)
+    }
+
     /// Read and deserialize the expected results file.
+    #[cfg(feature = "serde")]
     pub(crate) fn read_expected_serialized<T: DeserializeOwned>(
-        &mut self,
+        &self,
+        actual: &[u8],
     ) -> Result<T, ExpectError> {
-        let serializer = DefaultSerializer::default();
-        let reader = self.get_expected_reader(serializer.get_extension())?;
+        let serializer = self.get_serializer();
+        let reader = self.get_expected_reader(actual, serializer.get_extension())?;
         serializer.deserialize(reader)
     }
 
+    /// Deserialize the serialized actual results.
+    #[cfg(feature = "serde")]
+    pub(crate) fn deserialize_actual<T: DeserializeOwned>(
+        &self,
+        actual: &[u8],
+    ) -> Result<T, ExpectError> {
+        self.get_serializer()
+            .deserialize(BufReader::new(actual))
+            .map_err(|e| match e {
+                ExpectError::DeserializeExpected(e) => ExpectError::DeserializeActual(e),
+                e => e,
+            })
+    }
+
     /// Get a [`BufReader`] for the expected results file.
-    fn get_expected_reader(&mut self, extension: &str) -> Result<BufReader<File>, ExpectError> {
+    fn get_expected_reader(
+        &self,
+        actual: &[u8],
+        extension: &str,
+    ) -> Result<BufReader<File>, ExpectError> {
         let path = self.get_expected_path(extension);
-        if !path.is_file() {
-            println!("Creating expected file: {}", path.display());
-            let actual = self.get_actual_path(extension);
-            copy(&actual, &path).map_err(|e| ExpectError::CopyActual(e, actual, path.clone()))?;
+        let exists = path.is_file();
+        match self.settings.get_update_mode() {
+            UpdateMode::Missing | UpdateMode::Never if exists => {}
+            UpdateMode::Missing => {
+                println!("Creating expected file: {}", path.display());
+                self.write_expected(actual, extension)?;
+            }
+            UpdateMode::Always => {
+                println!("Updating expected file: {}", path.display());
+                self.write_expected(actual, extension)?;
+            }
+            UpdateMode::Never => return Err(ExpectError::ExpectedNotFound(path)),
         }
         let file = File::open(&path).map_err(|e| ExpectError::OpenExpected(e, path))?;
         Ok(BufReader::new(file))
     }
+
+    /// Write the actual results to the expected results file.
+    fn write_expected(&self, actual: &[u8], extension: &str) -> Result<(), ExpectError> {
+        let path = self.get_expected_path(extension);
+        write(&path, actual).map_err(|e| ExpectError::WriteExpected(e, path))?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -40,24 +95,65 @@ mod tests {
     #[test]
     fn read_expected_text() -> Result<(), ExpectError> {
         // Arrange
-        let mut expect = Expect::new();
+        let expect = Expect::new();
         let expected = "Hello, world!";
         // Act
-        let result = expect.read_expected_text(TEXT_EXT)?;
+        let result = expect.read_expected_text("Ignored", TEXT_EXT)?;
         // Assert
         assert_eq!(result, expected);
         Ok(())
     }
 
     #[test]
+    fn read_expected_text_never_update() {
+        // Arrange
+        let expect = Expect::builder()
+            .with_update_mode(UpdateMode::Never)
+            .build();
+        // Act
+        let result = expect.read_expected_text("Hello, world!", TEXT_EXT);
+        // Assert
+        assert!(matches!(result, Err(ExpectError::ExpectedNotFound(_))));
+    }
+
+    #[test]
+    fn read_expected_text_always_update() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::builder()
+            .with_update_mode(UpdateMode::Always)
+            .build();
+        expect.verify_dirs()?;
+        // Act
+        let result = expect.read_expected_text("Hello, world!", TEXT_EXT)?;
+        // Assert
+        assert_eq!(result, "Hello, world!");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
     fn read_expected_serialized() -> Result<(), ExpectError> {
         // Arrange
-        let mut expect = Expect::new();
+        let expect = Expect::new();
         let expected = SampleStruct::sample();
         // Act
-        let result: SampleStruct = expect.read_expected_serialized()?;
+        let result: SampleStruct = expect.read_expected_serialized(&[])?;
         // Assert
         assert_eq!(result, expected);
         Ok(())
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_actual() -> Result<(), ExpectError> {
+        // Arrange
+        let expect = Expect::new();
+        let actual = SampleStruct::sample();
+        let serialized = expect.serialize_actual(&actual)?;
+        // Act
+        let result: SampleStruct = expect.deserialize_actual(&serialized)?;
+        // Assert
+        assert_eq!(result, actual);
+        Ok(())
+    }
 }
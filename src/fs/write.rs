@@ -1,31 +1,42 @@
 use crate::prelude::*;
 
 impl Expect {
-    /// Write the actual results to a file.
-    pub(crate) fn write_actual_text(
-        &mut self,
-        actual: &str,
+    /// Write the actual results to a file as raw bytes.
+    pub(crate) fn write_actual_bytes(
+        &self,
+        actual: &[u8],
         extension: &str,
     ) -> Result<(), ExpectError> {
         let mut writer = self.get_actual_writer(extension)?;
-        writer
-            .write_all(actual.as_bytes())
-            .map_err(ExpectError::WriteActual)?;
+        writer.write_all(actual).map_err(ExpectError::WriteActual)?;
         Ok(())
     }
 
-    /// Serialize the actual results and write to a file.
-    pub(crate) fn write_actual_serialized<T: Serialize>(
-        &mut self,
+    /// Serialize the actual results to scrubbed text.
+    #[cfg(feature = "serde")]
+    pub(crate) fn serialize_actual<T: Serialize>(
+        &self,
         actual: &T,
-    ) -> Result<(), ExpectError> {
-        let serializer = DefaultSerializer::default();
-        let writer = self.get_actual_writer(serializer.get_extension())?;
-        serializer.serialize(writer, actual)
+    ) -> Result<Vec<u8>, ExpectError> {
+        let mut bytes = Vec::new();
+        self.get_serializer()
+            .serialize(BufWriter::new(&mut bytes), actual)?;
+        let text =
+            String::from_utf8(bytes).map_err(|e| ExpectError::SerializeActual(Box::new(e)))?;
+        Ok(self.scrub(&text).into_bytes())
+    }
+
+    /// Remove the actual results file.
+    pub(crate) fn remove_actual(&self, extension: &str) -> Result<(), ExpectError> {
+        let path = self.get_actual_path(extension);
+        if path.is_file() {
+            remove_file(&path).map_err(|e| ExpectError::RemoveActual(e, path))?;
+        }
+        Ok(())
     }
 
     /// Get a [`BufWriter`] for the actual results file.
-    fn get_actual_writer(&mut self, extension: &str) -> Result<BufWriter<File>, ExpectError> {
+    fn get_actual_writer(&self, extension: &str) -> Result<BufWriter<File>, ExpectError> {
         let path = self.get_actual_path(extension);
         let file = File::create(&path).map_err(|e| ExpectError::CreateActual(e, path))?;
         Ok(BufWriter::new(file))
@@ -37,16 +48,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn write_actual_text() -> Result<(), ExpectError> {
+    fn write_actual_bytes() -> Result<(), ExpectError> {
         // Arrange
-        let mut expect = Expect::new();
+        let expect = Expect::new();
         let actual = "Hello, world!";
         let path = expect.get_actual_path(TEXT_EXT);
         if path.exists() {
             remove_file(&path).expect("Should be able to remove file");
         }
         // Act
-        expect.write_actual_text(actual, TEXT_EXT)?;
+        expect.write_actual_bytes(actual.as_bytes(), TEXT_EXT)?;
         // Assert
         let result = read_to_string(path).expect("Should be able to read file");
         assert_eq!(result, actual);
@@ -54,19 +65,14 @@ mod tests {
     }
 
     #[test]
-    fn write_actual_serialized() -> Result<(), ExpectError> {
+    #[cfg(feature = "serde")]
+    fn serialize_actual() -> Result<(), ExpectError> {
         // Arrange
-        let mut expect = Expect::new();
+        let expect = Expect::new();
         let actual = SampleStruct::sample();
-        let extension = DefaultSerializer::default().get_extension();
-        let path = expect.get_actual_path(extension);
-        if path.exists() {
-            remove_file(&path).expect("Should be able to remove file");
-        }
         // Act
-        expect.write_actual_serialized(&actual)?;
+        let result = expect.serialize_actual(&actual)?;
         // Assert
-        let result = read_to_string(path).expect("Should be able to read file");
         assert!(!result.is_empty());
         Ok(())
     }
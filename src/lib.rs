@@ -1,11 +1,39 @@
+mod builder;
+mod command;
+#[cfg(feature = "config")]
+mod config;
 mod context;
+mod debug;
 mod diff;
+mod directory;
 mod error;
 mod expect;
+mod fixture;
 mod fs;
+mod mismatches;
+mod panic;
 pub(crate) mod prelude;
 #[cfg(test)]
 mod samples;
+mod scope;
+mod scrubber;
+#[cfg(feature = "serde")]
 mod serialization;
+mod settings;
+mod update;
 
+pub use builder::ExpectBuilder;
+pub use diff::DiffOptions;
 pub use expect::Expect;
+pub use scope::settings;
+pub use scrubber::Scrubber;
+#[cfg(feature = "serde")]
+pub use serialization::Format;
+#[cfg(feature = "json")]
+pub use serialization::JsonOptions;
+#[cfg(feature = "yaml")]
+pub use serialization::YamlOptions;
+pub use update::UpdateMode;
+
+#[cfg(all(feature = "serde", not(any(feature = "yaml", feature = "json"))))]
+compile_error!("The serde feature requires the yaml or json feature.");
@@ -0,0 +1,69 @@
+use crate::prelude::*;
+use std::fmt::Write as _;
+use std::mem::take;
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+/// Paths of the mismatched snapshots recorded in soft mode.
+///
+/// Shared by each clone of an [`Expect`] and reported when the last clone is dropped.
+#[derive(Debug, Default)]
+pub(crate) struct Mismatches(Mutex<Vec<PathBuf>>);
+
+impl Mismatches {
+    /// Record the path of a mismatched snapshot.
+    pub(crate) fn push(&self, path: PathBuf) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(path);
+    }
+
+    /// Panic with a report of the recorded mismatches.
+    ///
+    /// The recorded mismatches are cleared so they are only reported once.
+    #[allow(clippy::panic)]
+    pub(crate) fn report(&self) {
+        let mismatches = take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner));
+        if mismatches.is_empty() {
+            return;
+        }
+        let mut report = format!("{} snapshots did not match:", mismatches.len());
+        for path in mismatches {
+            write!(report, "\n- {}", path.display()).expect("Should write to string");
+        }
+        panic!("{report}");
+    }
+}
+
+impl Drop for Mismatches {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.report();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn report() {
+        // Arrange
+        let mismatches = Mismatches::default();
+        mismatches.push(PathBuf::from("a.expect.txt"));
+        mismatches.push(PathBuf::from("b.expect.txt"));
+        // Act
+        let result = capture_panic(AssertUnwindSafe(|| mismatches.report()));
+        let again = capture_panic(AssertUnwindSafe(|| mismatches.report()));
+        // Assert
+        let result = result.expect("Should panic");
+        assert_eq!(
+            result.message,
+            "2 snapshots did not match:\n- a.expect.txt\n- b.expect.txt"
+        );
+        assert_eq!(again, None, "Should only report once");
+    }
+}
@@ -0,0 +1,111 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+use std::panic;
+use std::panic::{AssertUnwindSafe, UnwindSafe};
+use std::sync::Once;
+
+static INSTALL_HOOK: Once = Once::new();
+
+thread_local! {
+    static IS_CAPTURING: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Message and location of a captured panic.
+#[derive(Debug, PartialEq)]
+pub(crate) struct PanicSnapshot {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for PanicSnapshot {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Message: {}", self.message)?;
+        let location = self.location.as_deref().unwrap_or("[Unknown]");
+        writeln!(f, "Location: {location}")
+    }
+}
+
+/// Run the closure and capture the panic, if any.
+///
+/// The panic is not printed while it is being captured.
+pub(crate) fn capture_panic<F: FnOnce() -> R + UnwindSafe, R>(f: F) -> Option<PanicSnapshot> {
+    install_hook();
+    LOCATION.set(None);
+    IS_CAPTURING.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IS_CAPTURING.set(false);
+    let payload = result.err()?;
+    Some(PanicSnapshot {
+        message: get_message(payload.as_ref()),
+        location: LOCATION.take(),
+    })
+}
+
+/// Install a panic hook that records the location of captured panics.
+///
+/// Panics that are not being captured are passed to the previous hook.
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CAPTURING.get() {
+                LOCATION.set(info.location().map(ToString::to_string));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn get_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "[Non-string panic payload]".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn capture_panic_message() {
+        // Arrange
+        // Act
+        let result = capture_panic(|| sample_panic("Hello, world!"));
+        // Assert
+        let result = result.expect("Should capture panic");
+        assert_eq!(result.message, "Hello, world!");
+        let location = result.location.expect("Should capture location");
+        assert!(location.starts_with("src/samples/panic.rs:"), "{location}");
+    }
+
+    #[test]
+    fn capture_panic_none() {
+        // Arrange
+        // Act
+        let result = capture_panic(|| 1 + 1);
+        // Assert
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn get_message_payloads() {
+        // Arrange
+        let str: Box<dyn Any + Send> = Box::new("str");
+        let string: Box<dyn Any + Send> = Box::new("string".to_owned());
+        let other: Box<dyn Any + Send> = Box::new(1);
+        // Act
+        // Assert
+        assert_eq!(get_message(str.as_ref()), "str");
+        assert_eq!(get_message(string.as_ref()), "string");
+        assert_eq!(get_message(other.as_ref()), "[Non-string panic payload]");
+    }
+}
@@ -1,14 +1,36 @@
 #![allow(unused_imports)]
+pub(crate) use crate::builder::*;
+pub(crate) use crate::command::*;
+#[cfg(feature = "config")]
+pub(crate) use crate::config::*;
 pub(crate) use crate::context::*;
+pub(crate) use crate::debug::*;
 pub(crate) use crate::diff::*;
+pub(crate) use crate::directory::*;
 pub(crate) use crate::error::*;
 pub(crate) use crate::expect::*;
+pub(crate) use crate::fixture::*;
 pub(crate) use crate::fs::*;
+pub(crate) use crate::mismatches::*;
+pub(crate) use crate::panic::*;
+#[cfg(all(test, feature = "serde"))]
+pub(crate) use crate::samples::error::*;
 #[cfg(test)]
+pub(crate) use crate::samples::helper::*;
+#[cfg(test)]
+pub(crate) use crate::samples::panic::*;
+#[cfg(all(test, feature = "serde"))]
 pub(crate) use crate::samples::value::*;
+pub(crate) use crate::scope::*;
+pub(crate) use crate::scrubber::*;
+#[cfg(feature = "serde")]
 pub(crate) use crate::serialization::*;
-pub(crate) use colored::Colorize;
+pub(crate) use crate::settings::*;
+pub(crate) use crate::update::*;
+pub(crate) use colored::{Color, Colorize};
+#[cfg(feature = "serde")]
 pub(crate) use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
 pub(crate) use serde::{Deserialize, Serialize};
 pub(crate) use std::fmt::Debug;
 pub(crate) use std::fs::*;
@@ -0,0 +1,25 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+#[derive(Debug)]
+pub(crate) struct SampleError(pub io::Error);
+
+impl SampleError {
+    pub(crate) fn sample(message: &str) -> Self {
+        Self(io::Error::other(message.to_owned()))
+    }
+}
+
+impl Display for SampleError {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to load sample")
+    }
+}
+
+impl Error for SampleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
@@ -0,0 +1,13 @@
+use crate::prelude::*;
+use std::panic::Location;
+
+/// Shared assertion helper that passes through the location of the calling test.
+#[track_caller]
+pub(crate) fn sample_helper() -> Expect {
+    Expect::new()
+}
+
+/// Shared assertion helper that is given the location of the calling test.
+pub(crate) fn sample_helper_with_location(location: &'static Location<'static>) -> Expect {
+    Expect::from_location(location)
+}
@@ -1 +1,6 @@
+#[cfg(feature = "serde")]
+pub(crate) mod error;
+pub(crate) mod helper;
+pub(crate) mod panic;
+#[cfg(feature = "serde")]
 pub(crate) mod value;
@@ -0,0 +1,4 @@
+#[allow(clippy::panic)]
+pub(crate) fn sample_panic(message: &str) -> ! {
+    panic!("{message}");
+}
@@ -0,0 +1,113 @@
+use crate::prelude::*;
+use std::cell::RefCell;
+
+thread_local! {
+    /// Settings bound to the current thread by [`ExpectBuilder::bind`].
+    static SCOPED: RefCell<Option<Settings>> = const { RefCell::new(None) };
+}
+
+/// Create an [`ExpectBuilder`] from the settings of the current scope.
+///
+/// Bind the settings to apply them to each [`Expect`] created in a closure.
+///
+/// Example: `expect::settings().with_scrubber(scrubber).bind(|| { ... })`
+#[track_caller]
+pub fn settings() -> ExpectBuilder {
+    Expect::builder()
+}
+
+/// Get the settings bound to the current thread.
+pub(crate) fn get_scoped_settings() -> Option<Settings> {
+    SCOPED.with(|scoped| scoped.borrow().clone())
+}
+
+/// Restores the previously bound settings when dropped.
+pub(crate) struct ScopeGuard {
+    previous: Option<Settings>,
+}
+
+impl ScopeGuard {
+    /// Bind the settings to the current thread until the guard is dropped.
+    pub(crate) fn bind(settings: Settings) -> Self {
+        let previous = SCOPED.with(|scoped| scoped.replace(Some(settings)));
+        Self { previous }
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SCOPED.with(|scoped| scoped.replace(previous));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn bind() {
+        // Arrange
+        let scrubber = Scrubber::replace("secret", "[REDACTED]");
+        // Act
+        let (inner, helper) = settings()
+            .with_snapshot_dir(".snapshots")
+            .with_scrubber(scrubber)
+            .bind(|| (Expect::new(), sample_helper()));
+        let outer = Expect::new();
+        // Assert
+        assert_eq!(
+            inner.get_expected_path(TEXT_EXT),
+            PathBuf::from("src/.snapshots/scope/bind.expect.txt")
+        );
+        assert_eq!(inner.settings.scrubbers.len(), 1);
+        assert_eq!(helper.settings.scrubbers.len(), 1, "Helper");
+        assert!(outer.settings.scrubbers.is_empty(), "Restored");
+        assert_eq!(outer.settings.snapshot_dir, EXPECT_DIR, "Restored");
+    }
+
+    #[test]
+    fn bind_scrubber() -> Result<(), ExpectError> {
+        // Arrange
+        let scrubber = Scrubber::replace("secret", "[REDACTED]");
+        // Act
+        let result = settings()
+            .with_scrubber(scrubber)
+            .bind(|| sample_helper().string("a secret", TEXT_EXT))?;
+        // Assert
+        assert!(result);
+        Ok(())
+    }
+
+    #[test]
+    fn bind_nested() {
+        // Arrange
+        // Act
+        let (inner, middle) = settings().with_snapshot_dir(".snapshots").bind(|| {
+            let inner = settings()
+                .with_expected_extension("approved")
+                .bind(Expect::new);
+            (inner, Expect::new())
+        });
+        // Assert
+        assert_eq!(inner.settings.snapshot_dir, ".snapshots", "Inherited");
+        assert_eq!(inner.settings.expected_ext, "approved");
+        assert_eq!(middle.settings.expected_ext, EXPECT_EXT, "Restored");
+    }
+
+    #[test]
+    fn bind_panic() {
+        // Arrange
+        // Act
+        let result = catch_unwind(|| {
+            settings()
+                .with_snapshot_dir(".snapshots")
+                .bind(|| sample_panic("Hello, world!"));
+        });
+        let expect = Expect::new();
+        // Assert
+        assert!(result.is_err());
+        assert_eq!(expect.settings.snapshot_dir, EXPECT_DIR, "Restored");
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Replace non-deterministic text before it is compared.
+///
+/// Example: timestamps, temporary paths or process ids.
+#[derive(Clone)]
+pub struct Scrubber(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl Scrubber {
+    /// Create a new [`Scrubber`] from a function.
+    #[must_use]
+    pub fn new<F: Fn(&str) -> String + Send + Sync + 'static>(scrub: F) -> Self {
+        Self(Arc::new(scrub))
+    }
+
+    /// Create a new [`Scrubber`] that replaces every occurrence of `from` with `to`.
+    #[must_use]
+    pub fn replace(from: &str, to: &str) -> Self {
+        let from = from.to_owned();
+        let to = to.to_owned();
+        Self::new(move |text| text.replace(&from, &to))
+    }
+
+    /// Apply the [`Scrubber`] to the text.
+    pub(crate) fn scrub(&self, text: &str) -> String {
+        (self.0)(text)
+    }
+}
+
+impl Debug for Scrubber {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Scrubber")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace() {
+        // Arrange
+        let scrubber = Scrubber::replace("/tmp/abc123", "[TEMP]");
+        // Act
+        let result = scrubber.scrub("Wrote /tmp/abc123/a.txt and /tmp/abc123/b.txt");
+        // Assert
+        assert_eq!(result, "Wrote [TEMP]/a.txt and [TEMP]/b.txt");
+    }
+}
@@ -0,0 +1,116 @@
+use crate::prelude::*;
+
+/// Format of serialized snapshots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Format {
+    /// Single YAML document.
+    #[cfg(feature = "yaml")]
+    #[default]
+    Yaml,
+    /// YAML with one document per item.
+    #[cfg(feature = "yaml")]
+    YamlDocuments,
+    /// Single JSON document.
+    #[cfg(feature = "json")]
+    #[cfg_attr(not(feature = "yaml"), default)]
+    Json,
+    /// JSON Lines with one item per line.
+    #[cfg(feature = "json")]
+    JsonLines,
+}
+
+/// [`Serializer`] for the [`Format`] selected at runtime.
+#[derive(Debug, Default)]
+pub(crate) struct FormatSerializer {
+    format: Format,
+    #[cfg(feature = "yaml")]
+    yaml: YamlOptions,
+    #[cfg(feature = "json")]
+    json: JsonOptions,
+}
+
+impl From<&Settings> for FormatSerializer {
+    fn from(settings: &Settings) -> Self {
+        Self {
+            format: settings.format,
+            #[cfg(feature = "yaml")]
+            yaml: settings.yaml.clone(),
+            #[cfg(feature = "json")]
+            json: settings.json.clone(),
+        }
+    }
+}
+
+impl Serializer for FormatSerializer {
+    fn get_extension(&self) -> &'static str {
+        match self.format {
+            #[cfg(feature = "yaml")]
+            Format::Yaml => YamlSerializer::new(self.yaml.clone()).get_extension(),
+            #[cfg(feature = "yaml")]
+            Format::YamlDocuments => {
+                YamlDocumentsSerializer::new(self.yaml.clone()).get_extension()
+            }
+            #[cfg(feature = "json")]
+            Format::Json => JsonSerializer::new(self.json.clone()).get_extension(),
+            #[cfg(feature = "json")]
+            Format::JsonLines => JsonLinesSerializer.get_extension(),
+        }
+    }
+
+    fn get_item_label(&self, index: usize) -> String {
+        match self.format {
+            #[cfg(feature = "yaml")]
+            Format::Yaml => YamlSerializer::new(self.yaml.clone()).get_item_label(index),
+            #[cfg(feature = "yaml")]
+            Format::YamlDocuments => {
+                YamlDocumentsSerializer::new(self.yaml.clone()).get_item_label(index)
+            }
+            #[cfg(feature = "json")]
+            Format::Json => JsonSerializer::new(self.json.clone()).get_item_label(index),
+            #[cfg(feature = "json")]
+            Format::JsonLines => JsonLinesSerializer.get_item_label(index),
+        }
+    }
+
+    fn serialize<W: Sized + Write, T: Serialize>(
+        &self,
+        writer: BufWriter<W>,
+        value: &T,
+    ) -> Result<(), ExpectError> {
+        match self.format {
+            #[cfg(feature = "yaml")]
+            Format::Yaml => YamlSerializer::new(self.yaml.clone()).serialize(writer, value),
+            #[cfg(feature = "yaml")]
+            Format::YamlDocuments => {
+                YamlDocumentsSerializer::new(self.yaml.clone()).serialize(writer, value)
+            }
+            #[cfg(feature = "json")]
+            Format::Json => JsonSerializer::new(self.json.clone()).serialize(writer, value),
+            #[cfg(feature = "json")]
+            Format::JsonLines => JsonLinesSerializer.serialize(writer, value),
+        }
+    }
+
+    fn deserialize<R: Read + Sized, T: DeserializeOwned>(
+        &self,
+        reader: BufReader<R>,
+    ) -> Result<T, ExpectError> {
+        match self.format {
+            #[cfg(feature = "yaml")]
+            Format::Yaml => YamlSerializer::new(self.yaml.clone()).deserialize(reader),
+            #[cfg(feature = "yaml")]
+            Format::YamlDocuments => {
+                YamlDocumentsSerializer::new(self.yaml.clone()).deserialize(reader)
+            }
+            #[cfg(feature = "json")]
+            Format::Json => JsonSerializer::new(self.json.clone()).deserialize(reader),
+            #[cfg(feature = "json")]
+            Format::JsonLines => JsonLinesSerializer.deserialize(reader),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::prelude::*;
+use serde::ser;
+use serde::ser::{Impossible, SerializeSeq, SerializeTuple};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Write each item of a serialized sequence.
+pub(crate) trait ItemWriter {
+    /// Write a single item.
+    fn write_item<T: ?Sized + Serialize>(&mut self, item: &T) -> Result<(), ExpectError>;
+}
+
+/// Write each item of a sequence, or the value as a single item if it is not a sequence.
+pub(crate) fn write_items<T: Serialize, W: ItemWriter>(
+    writer: &mut W,
+    value: &T,
+) -> Result<(), ExpectError> {
+    match value.serialize(ItemsSerializer {
+        writer: &mut *writer,
+    }) {
+        Ok(()) => Ok(()),
+        Err(ItemsError::NotSequence) => writer.write_item(value),
+        Err(ItemsError::Item(e)) => Err(e),
+        Err(ItemsError::Custom(message)) => Err(ExpectError::SerializeActual(message.into())),
+    }
+}
+
+/// Serialize each item of a sequence with an [`ItemWriter`].
+///
+/// Other values are rejected with [`ItemsError::NotSequence`] before anything is written.
+struct ItemsSerializer<'a, W: ItemWriter> {
+    writer: &'a mut W,
+}
+
+/// Reject a value that is not a sequence.
+macro_rules! reject_not_sequence {
+    ($($method:ident($($ty:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<$ok, ItemsError> {
+                Err(ItemsError::NotSequence)
+            }
+        )*
+    };
+}
+
+impl<W: ItemWriter> ser::Serializer for ItemsSerializer<'_, W> {
+    type Ok = ();
+    type Error = ItemsError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), ItemsError>;
+    type SerializeTupleVariant = Impossible<(), ItemsError>;
+    type SerializeMap = Impossible<(), ItemsError>;
+    type SerializeStruct = Impossible<(), ItemsError>;
+    type SerializeStructVariant = Impossible<(), ItemsError>;
+
+    reject_not_sequence! {
+        serialize_bool(bool) -> ();
+        serialize_i8(i8) -> ();
+        serialize_i16(i16) -> ();
+        serialize_i32(i32) -> ();
+        serialize_i64(i64) -> ();
+        serialize_i128(i128) -> ();
+        serialize_u8(u8) -> ();
+        serialize_u16(u16) -> ();
+        serialize_u32(u32) -> ();
+        serialize_u64(u64) -> ();
+        serialize_u128(u128) -> ();
+        serialize_f32(f32) -> ();
+        serialize_f64(f64) -> ();
+        serialize_char(char) -> ();
+        serialize_str(&str) -> ();
+        serialize_bytes(&[u8]) -> ();
+        serialize_none() -> ();
+        serialize_unit() -> ();
+        serialize_unit_struct(&'static str) -> ();
+        serialize_unit_variant(&'static str, u32, &'static str) -> ();
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<(), ItemsError> {
+        Err(ItemsError::NotSequence)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), ItemsError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), ItemsError> {
+        Err(ItemsError::NotSequence)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self, ItemsError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, ItemsError> {
+        Ok(self)
+    }
+}
+
+impl<W: ItemWriter> SerializeSeq for ItemsSerializer<'_, W> {
+    type Ok = ();
+    type Error = ItemsError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), ItemsError> {
+        self.writer.write_item(value).map_err(ItemsError::Item)
+    }
+
+    fn end(self) -> Result<(), ItemsError> {
+        Ok(())
+    }
+}
+
+impl<W: ItemWriter> SerializeTuple for ItemsSerializer<'_, W> {
+    type Ok = ();
+    type Error = ItemsError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), ItemsError> {
+        self.writer.write_item(value).map_err(ItemsError::Item)
+    }
+
+    fn end(self) -> Result<(), ItemsError> {
+        Ok(())
+    }
+}
+
+/// Error serializing the items of a sequence.
+#[derive(Debug)]
+enum ItemsError {
+    /// The value is not a sequence.
+    NotSequence,
+    Item(ExpectError),
+    Custom(String),
+}
+
+impl Display for ItemsError {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemsError::NotSequence => write!(f, "Value is not a sequence"),
+            ItemsError::Item(e) => write!(f, "{e}"),
+            ItemsError::Custom(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Error for ItemsError {}
+
+impl ser::Error for ItemsError {
+    fn custom<T: Display>(message: T) -> Self {
+        ItemsError::Custom(message.to_string())
+    }
+}
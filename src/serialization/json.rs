@@ -1,18 +1,31 @@
 use crate::prelude::*;
 
 #[derive(Debug, Default)]
-pub(crate) struct JsonSerializer;
+pub(crate) struct JsonSerializer {
+    options: JsonOptions,
+}
+
+impl JsonSerializer {
+    /// Create a new [`JsonSerializer`].
+    pub(crate) fn new(options: JsonOptions) -> Self {
+        Self { options }
+    }
+}
 
 impl Serializer for JsonSerializer {
-    const EXTENSION: &'static str = "json";
+    fn get_extension(&self) -> &'static str {
+        "json"
+    }
 
     fn serialize<W: Sized + Write, T: Serialize>(
         &self,
         mut writer: BufWriter<W>,
         value: &T,
     ) -> Result<(), ExpectError> {
-        serde_json::to_writer_pretty(&mut writer, value)
-            .map_err(|e| ExpectError::SerializeActual(Box::new(e)))?;
+        let json = format_json(value, &self.options)?;
+        writer
+            .write_all(json.as_bytes())
+            .map_err(ExpectError::WriteActual)?;
         writer.flush().map_err(ExpectError::FlushActual)?;
         Ok(())
     }
@@ -0,0 +1,336 @@
+use crate::prelude::*;
+use serde::Deserializer;
+use serde::de::{MapAccess, Visitor};
+use serde_json::ser::PrettyFormatter;
+use serde_json::value::RawValue;
+use std::fmt::Formatter;
+
+/// Options for writing JSON.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct JsonOptions {
+    /// Number of spaces per level of indentation.
+    pub indent: usize,
+    /// Sort the keys of objects.
+    pub sort_keys: bool,
+    /// End the file with a newline.
+    pub trailing_newline: bool,
+    /// Write arrays that only contain scalars on a single line.
+    pub compact_scalar_arrays: bool,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            sort_keys: false,
+            trailing_newline: false,
+            compact_scalar_arrays: false,
+        }
+    }
+}
+
+/// Serialize a value to JSON with the [`JsonOptions`].
+pub(crate) fn format_json<T: Serialize + ?Sized>(
+    value: &T,
+    options: &JsonOptions,
+) -> Result<String, ExpectError> {
+    let mut json = if options.sort_keys || options.compact_scalar_arrays {
+        let compact =
+            serde_json::to_string(value).map_err(|e| ExpectError::SerializeActual(Box::new(e)))?;
+        let mut node =
+            JsonNode::parse(&compact).map_err(|e| ExpectError::SerializeActual(Box::new(e)))?;
+        if options.sort_keys {
+            node.sort_keys();
+        }
+        let mut json = String::new();
+        node.write(&mut json, options, 0);
+        json
+    } else {
+        let indent = " ".repeat(options.indent);
+        let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+        let mut buffer = Vec::new();
+        let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+        value
+            .serialize(&mut serializer)
+            .map_err(|e| ExpectError::SerializeActual(Box::new(e)))?;
+        String::from_utf8(buffer).expect("Should be valid UTF-8")
+    };
+    if options.trailing_newline {
+        json.push('\n');
+    }
+    Ok(json)
+}
+
+/// JSON value that preserves the order of object keys.
+enum JsonNode {
+    /// Scalar formatted as JSON.
+    Scalar(String),
+    Array(Vec<JsonNode>),
+    Object(Vec<(String, JsonNode)>),
+}
+
+impl JsonNode {
+    /// Parse compact JSON.
+    ///
+    /// Scalars are kept verbatim so numbers are not rounded through `f64`.
+    fn parse(json: &str) -> Result<JsonNode, serde_json::Error> {
+        match json.as_bytes().first() {
+            Some(b'[') => {
+                let items: Vec<&RawValue> = serde_json::from_str(json)?;
+                let items = items
+                    .into_iter()
+                    .map(|item| JsonNode::parse(item.get()))
+                    .collect::<Result<_, _>>()?;
+                Ok(JsonNode::Array(items))
+            }
+            Some(b'{') => {
+                let JsonEntries(entries) = serde_json::from_str(json)?;
+                let entries = entries
+                    .into_iter()
+                    .map(|(key, value)| Ok((key, JsonNode::parse(value.get())?)))
+                    .collect::<Result<_, serde_json::Error>>()?;
+                Ok(JsonNode::Object(entries))
+            }
+            _ => Ok(JsonNode::Scalar(json.to_owned())),
+        }
+    }
+
+    fn sort_keys(&mut self) {
+        match self {
+            JsonNode::Scalar(_) => {}
+            JsonNode::Array(items) => items.iter_mut().for_each(JsonNode::sort_keys),
+            JsonNode::Object(entries) => {
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                for (_, value) in entries.iter_mut() {
+                    value.sort_keys();
+                }
+            }
+        }
+    }
+
+    fn write(&self, out: &mut String, options: &JsonOptions, level: usize) {
+        let indent = " ".repeat(options.indent * (level + 1));
+        let outdent = " ".repeat(options.indent * level);
+        match self {
+            JsonNode::Scalar(scalar) => out.push_str(scalar),
+            JsonNode::Array(items) if items.is_empty() => out.push_str("[]"),
+            JsonNode::Array(items)
+                if options.compact_scalar_arrays
+                    && items.iter().all(|item| matches!(item, JsonNode::Scalar(_))) =>
+            {
+                out.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    item.write(out, options, level + 1);
+                }
+                out.push(']');
+            }
+            JsonNode::Array(items) => {
+                out.push_str("[\n");
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(",\n");
+                    }
+                    out.push_str(&indent);
+                    item.write(out, options, level + 1);
+                }
+                out.push('\n');
+                out.push_str(&outdent);
+                out.push(']');
+            }
+            JsonNode::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            JsonNode::Object(entries) => {
+                out.push_str("{\n");
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(",\n");
+                    }
+                    out.push_str(&indent);
+                    out.push_str(&serde_json::to_string(key).expect("Key should serialize"));
+                    out.push_str(": ");
+                    value.write(out, options, level + 1);
+                }
+                out.push('\n');
+                out.push_str(&outdent);
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// Entries of a JSON object in the order they are written.
+struct JsonEntries<'a>(Vec<(String, &'a RawValue)>);
+
+impl<'de> Deserialize<'de> for JsonEntries<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(JsonEntriesVisitor)
+    }
+}
+
+struct JsonEntriesVisitor;
+
+impl<'de> Visitor<'de> for JsonEntriesVisitor {
+    type Value = JsonEntries<'de>;
+
+    #[allow(clippy::absolute_paths)]
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a JSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonEntries<'de>, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(JsonEntries(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct SampleJson {
+        name: String,
+        values: Vec<f32>,
+        nested: Vec<Vec<i32>>,
+        empty: Vec<i32>,
+        map: BTreeMap<String, SampleEnum>,
+    }
+
+    fn sample_json() -> SampleJson {
+        let mut map = BTreeMap::new();
+        map.insert("b".to_owned(), SampleEnum::B);
+        map.insert("a".to_owned(), SampleEnum::A);
+        SampleJson {
+            name: "Hello, world!".to_owned(),
+            values: vec![7.2, 2.0],
+            nested: vec![vec![1, 2], vec![3]],
+            empty: Vec::new(),
+            map,
+        }
+    }
+
+    #[test]
+    fn format_json_default() -> Result<(), ExpectError> {
+        // Arrange
+        let value = SampleStruct::sample();
+        // Act
+        let result = format_json(&value, &JsonOptions::default())?;
+        // Assert
+        let expected = serde_json::to_string_pretty(&value).expect("Should serialize");
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_json_indent() -> Result<(), ExpectError> {
+        // Arrange
+        let options = JsonOptions {
+            indent: 4,
+            trailing_newline: true,
+            ..JsonOptions::default()
+        };
+        // Act
+        let result = format_json(&SampleEnum::A, &options)?;
+        let nested = format_json(&vec![vec![1]], &options)?;
+        // Assert
+        assert_eq!(result, "\"A\"\n");
+        assert_eq!(nested, "[\n    [\n        1\n    ]\n]\n");
+        Ok(())
+    }
+
+    #[test]
+    fn format_json_sort_keys() -> Result<(), ExpectError> {
+        // Arrange
+        let options = JsonOptions {
+            sort_keys: true,
+            ..JsonOptions::default()
+        };
+        let value = sample_json();
+        // Act
+        let result = format_json(&value, &options)?;
+        // Assert
+        let keys: Vec<&str> = result
+            .lines()
+            .filter(|line| line.starts_with("  \""))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "  \"empty\": [],",
+                "  \"map\": {",
+                "  \"name\": \"Hello, world!\",",
+                "  \"nested\": [",
+                "  \"values\": [",
+            ]
+        );
+        assert!(
+            result.contains("\"values\": [\n    7.2,\n    2.0\n  ]"),
+            "{result}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn format_json_compact_scalar_arrays() -> Result<(), ExpectError> {
+        // Arrange
+        let options = JsonOptions {
+            compact_scalar_arrays: true,
+            ..JsonOptions::default()
+        };
+        let value = sample_json();
+        // Act
+        let result = format_json(&value, &options)?;
+        // Assert
+        let expected = r#"{
+  "name": "Hello, world!",
+  "values": [7.2, 2.0],
+  "nested": [
+    [1, 2],
+    [3]
+  ],
+  "empty": [],
+  "map": {
+    "a": "A",
+    "b": "B"
+  }
+}"#;
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn format_json_large_integers() -> Result<(), ExpectError> {
+        // Arrange
+        let options = JsonOptions {
+            sort_keys: true,
+            compact_scalar_arrays: true,
+            ..JsonOptions::default()
+        };
+        let value = (u64::MAX, u128::MAX, i128::MIN, vec![1.5e300, -0.0]);
+        // Act
+        let result = format_json(&value, &options)?;
+        // Assert
+        let expected = r"[
+  18446744073709551615,
+  340282366920938463463374607431768211455,
+  -170141183460469231731687303715884105728,
+  [1.5e300, -0.0]
+]";
+        assert_eq!(result, expected);
+        let deserialized: (u64, u128, i128, Vec<f64>) =
+            serde_json::from_str(&result).expect("Should deserialize");
+        assert_eq!(deserialized, value);
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+use crate::prelude::*;
+use serde::de;
+use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_json::de::IoRead;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, Lines};
+
+/// Serialize sequences as JSON Lines with one item per line.
+///
+/// A value that is not a sequence is written as a single line.
+#[derive(Debug, Default)]
+pub(crate) struct JsonLinesSerializer;
+
+impl Serializer for JsonLinesSerializer {
+    fn get_extension(&self) -> &'static str {
+        "jsonl"
+    }
+
+    fn get_item_label(&self, index: usize) -> String {
+        format!("Item {}", index + 1)
+    }
+
+    fn serialize<W: Sized + Write, T: Serialize>(
+        &self,
+        mut writer: BufWriter<W>,
+        value: &T,
+    ) -> Result<(), ExpectError> {
+        let mut lines = LinesWriter::default();
+        write_items(&mut lines, value)?;
+        writer
+            .write_all(&lines.out)
+            .map_err(ExpectError::WriteActual)?;
+        writer.flush().map_err(ExpectError::FlushActual)?;
+        Ok(())
+    }
+
+    fn deserialize<R: Read + Sized, T: DeserializeOwned>(
+        &self,
+        reader: BufReader<R>,
+    ) -> Result<T, ExpectError> {
+        let deserializer = LinesDeserializer {
+            lines: reader.lines(),
+            line: 0,
+        };
+        T::deserialize(deserializer).map_err(|e| ExpectError::DeserializeExpected(Box::new(e)))
+    }
+}
+
+/// Write each item as a line of JSON.
+#[derive(Default)]
+struct LinesWriter {
+    out: Vec<u8>,
+}
+
+impl ItemWriter for LinesWriter {
+    fn write_item<T: ?Sized + Serialize>(&mut self, item: &T) -> Result<(), ExpectError> {
+        serde_json::to_writer(&mut self.out, item)
+            .map_err(|e| ExpectError::SerializeActual(Box::new(e)))?;
+        self.out.push(b'\n');
+        Ok(())
+    }
+}
+
+/// Deserialize each non-empty line as an item of a sequence.
+///
+/// A value that is not a sequence is deserialized from a single line.
+struct LinesDeserializer<R: Read> {
+    lines: Lines<BufReader<R>>,
+    /// Number of the most recently read line.
+    line: usize,
+}
+
+impl<R: Read> LinesDeserializer<R> {
+    /// Deserialize the only non-empty line.
+    fn single_line<T, F>(mut self, deserialize: F) -> Result<T, JsonLinesError>
+    where
+        F: for<'a> FnOnce(
+            &mut serde_json::Deserializer<IoRead<&'a [u8]>>,
+        ) -> Result<T, serde_json::Error>,
+    {
+        let mut found = None;
+        for line in self.lines.by_ref() {
+            self.line += 1;
+            let line = line.map_err(|e| JsonLinesError::Read(self.line, e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if found.is_some() {
+                return Err(JsonLinesError::NotSingleLine);
+            }
+            found = Some((self.line, line));
+        }
+        let (number, line) = found.ok_or(JsonLinesError::NotSingleLine)?;
+        let mut deserializer = serde_json::Deserializer::from_reader(line.as_bytes());
+        deserialize(&mut deserializer)
+            .and_then(|value| deserializer.end().map(|()| value))
+            .map_err(|e| JsonLinesError::Deserialize(number, e))
+    }
+}
+
+/// Deserialize a value that is not a sequence from a single line.
+macro_rules! deserialize_single_line {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.single_line(|deserializer| deserializer.$method(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de, R: Read> serde::Deserializer<'de> for LinesDeserializer<R> {
+    type Error = JsonLinesError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self)
+    }
+
+    deserialize_single_line! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_option
+        deserialize_unit deserialize_map deserialize_identifier
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single_line(|deserializer| deserializer.deserialize_unit_struct(name, visitor))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single_line(|deserializer| deserializer.deserialize_newtype_struct(name, visitor))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single_line(|deserializer| deserializer.deserialize_struct(name, fields, visitor))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single_line(|deserializer| deserializer.deserialize_enum(name, variants, visitor))
+    }
+
+    forward_to_deserialize_any! {
+        seq tuple tuple_struct ignored_any
+    }
+}
+
+impl<'de, R: Read> SeqAccess<'de> for LinesDeserializer<R> {
+    type Error = JsonLinesError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        for line in self.lines.by_ref() {
+            self.line += 1;
+            let line = line.map_err(|e| JsonLinesError::Read(self.line, e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut deserializer = serde_json::Deserializer::from_reader(line.as_bytes());
+            let item = seed
+                .deserialize(&mut deserializer)
+                .and_then(|item| deserializer.end().map(|()| item))
+                .map_err(|e| JsonLinesError::Deserialize(self.line, e))?;
+            return Ok(Some(item));
+        }
+        Ok(None)
+    }
+}
+
+/// Error deserializing JSON Lines.
+#[allow(clippy::absolute_paths)]
+#[derive(Debug)]
+enum JsonLinesError {
+    Read(usize, std::io::Error),
+    Deserialize(usize, serde_json::Error),
+    /// A value that is not a sequence must be a single line.
+    NotSingleLine,
+    Custom(String),
+}
+
+impl Display for JsonLinesError {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonLinesError::Read(line, e) => write!(f, "Line {line}: {e}"),
+            JsonLinesError::Deserialize(line, e) => write!(f, "Line {line}: {e}"),
+            JsonLinesError::NotSingleLine => write!(
+                f,
+                "Expected a single line for a value that is not a sequence"
+            ),
+            JsonLinesError::Custom(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Error for JsonLinesError {}
+
+impl de::Error for JsonLinesError {
+    fn custom<T: Display>(message: T) -> Self {
+        JsonLinesError::Custom(message.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = JsonLinesSerializer;
+        let values = vec![SampleEnum::A, SampleEnum::B];
+        // Act
+        let result = serializer.serialize_to_string(&values)?;
+        // Assert
+        assert_eq!(result, "\"A\"\n\"B\"\n");
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_not_sequence() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = JsonLinesSerializer;
+        // Act
+        let result = serializer.serialize_to_string(SampleEnum::A)?;
+        // Assert
+        assert_eq!(result, "\"A\"\n");
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = JsonLinesSerializer;
+        let reader = BufReader::new("\"A\"\n\n\"C\"\n".as_bytes());
+        // Act
+        let result: Vec<SampleEnum> = serializer.deserialize(reader)?;
+        // Assert
+        assert_eq!(result, vec![SampleEnum::A, SampleEnum::C]);
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_not_sequence() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = JsonLinesSerializer;
+        let sample = SampleStruct::sample();
+        let text = serializer.serialize_to_string(&sample)?;
+        // Act
+        let result: SampleStruct = serializer.deserialize(BufReader::new(text.as_bytes()))?;
+        // Assert
+        assert_eq!(result, sample);
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_not_sequence_lines() {
+        // Arrange
+        let serializer = JsonLinesSerializer;
+        let reader = BufReader::new("\"A\"\n\"B\"\n".as_bytes());
+        // Act
+        let result: Result<SampleEnum, ExpectError> = serializer.deserialize(reader);
+        // Assert
+        let error = result.expect_err("Should fail to deserialize");
+        assert!(error.to_string().contains("single line"), "{error}");
+    }
+
+    #[test]
+    fn deserialize_reports_line() {
+        // Arrange
+        let serializer = JsonLinesSerializer;
+        let reader = BufReader::new("\"A\"\n\n\"D\"\n".as_bytes());
+        // Act
+        let result: Result<Vec<SampleEnum>, ExpectError> = serializer.deserialize(reader);
+        // Assert
+        let error = result.expect_err("Should fail to deserialize");
+        assert!(error.to_string().contains("Line 3:"), "{error}");
+    }
+}
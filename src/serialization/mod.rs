@@ -1,17 +1,39 @@
-mod default;
+mod format;
+mod items;
 #[allow(dead_code)]
 #[cfg(feature = "json")]
 mod json;
+#[cfg(feature = "json")]
+mod json_formatter;
+#[cfg(feature = "json")]
+mod json_lines;
+mod result;
 mod serializer;
+mod value_tree;
 #[allow(dead_code)]
 #[cfg(feature = "yaml")]
 mod yaml;
+#[cfg(feature = "yaml")]
+mod yaml_documents;
+#[cfg(feature = "yaml")]
+mod yaml_options;
 
-pub(crate) use default::*;
+pub use format::*;
+pub(crate) use items::*;
 #[allow(unused_imports)]
 #[cfg(feature = "json")]
 pub(crate) use json::*;
+#[cfg(feature = "json")]
+pub use json_formatter::*;
+#[cfg(feature = "json")]
+pub(crate) use json_lines::*;
+pub(crate) use result::*;
 pub use serializer::*;
+pub(crate) use value_tree::*;
 #[allow(unused_imports)]
 #[cfg(feature = "yaml")]
 pub(crate) use yaml::*;
+#[cfg(feature = "yaml")]
+pub(crate) use yaml_documents::*;
+#[cfg(feature = "yaml")]
+pub use yaml_options::*;
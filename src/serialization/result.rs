@@ -0,0 +1,48 @@
+use crate::prelude::*;
+use std::error::Error;
+
+/// Serializable form of a [`Result`].
+///
+/// Errors are recorded as the message of each error in the [`Error::source`] chain.
+#[derive(Debug, Serialize)]
+pub(crate) enum ResultSnapshot<'a, T> {
+    Ok(&'a T),
+    Err(Vec<String>),
+}
+
+impl<'a, T, E: Error> From<&'a Result<T, E>> for ResultSnapshot<'a, T> {
+    fn from(result: &'a Result<T, E>) -> Self {
+        match result {
+            Ok(value) => ResultSnapshot::Ok(value),
+            Err(error) => ResultSnapshot::Err(get_error_chain(error)),
+        }
+    }
+}
+
+/// Get the [`Display`] message of the error and each of its sources.
+///
+/// [`Display`]: std::fmt::Display
+fn get_error_chain(error: &dyn Error) -> Vec<String> {
+    let mut messages = vec![error.to_string()];
+    let mut source = error.source();
+    while let Some(error) = source {
+        messages.push(error.to_string());
+        source = error.source();
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_error_chain_sources() {
+        // Arrange
+        let error = SampleError::sample("File not found");
+        // Act
+        let result = get_error_chain(&error);
+        // Assert
+        assert_eq!(result, ["Failed to load sample", "File not found"]);
+    }
+}
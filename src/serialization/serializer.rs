@@ -1,7 +1,13 @@
 use crate::prelude::*;
 
-pub trait Serializer: Default {
-    const EXTENSION: &'static str;
+pub trait Serializer {
+    /// Get the file extension of the serialized format.
+    fn get_extension(&self) -> &'static str;
+
+    /// Get the label of a sequence item when reporting differences.
+    fn get_item_label(&self, index: usize) -> String {
+        format!("Index {index}")
+    }
 
     /// Serialize a value.
     fn serialize<W: Sized + Write, T: Serialize>(
@@ -18,15 +24,10 @@ pub trait Serializer: Default {
 }
 
 pub trait SerializerExtensions<S: Serializer> {
-    fn get_extension(&self) -> &'static str;
     fn serialize_to_string<T: Serialize>(&self, value: T) -> Result<String, ExpectError>;
 }
 
 impl<S: Serializer> SerializerExtensions<S> for S {
-    fn get_extension(&self) -> &'static str {
-        Self::EXTENSION
-    }
-
     fn serialize_to_string<T: Serialize>(&self, value: T) -> Result<String, ExpectError> {
         let mut buffer = Vec::new();
         {
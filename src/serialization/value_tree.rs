@@ -0,0 +1,296 @@
+use crate::prelude::*;
+use serde::Deserializer;
+use serde::de;
+use serde::de::{EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{Error as _, SerializeMap, SerializeSeq};
+use std::cmp::Ordering;
+use std::fmt::Formatter;
+
+/// Format agnostic tree of a serialized value.
+///
+/// Mappings are compared regardless of the order of their entries.
+#[derive(Clone, Debug)]
+pub(crate) enum ValueTree {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Sequence(Vec<ValueTree>),
+    Mapping(Vec<(ValueTree, ValueTree)>),
+    Tagged(String, Box<ValueTree>),
+}
+
+impl ValueTree {
+    /// Round each float to the number of decimal places.
+    pub(crate) fn round_floats(&mut self, precision: u8) {
+        match self {
+            ValueTree::Float(value) => {
+                let factor = 10_f64.powi(i32::from(precision));
+                *value = (*value * factor).round() / factor;
+            }
+            ValueTree::Sequence(items) => {
+                for item in items {
+                    item.round_floats(precision);
+                }
+            }
+            ValueTree::Mapping(entries) => {
+                for (key, value) in entries {
+                    key.round_floats(precision);
+                    value.round_floats(precision);
+                }
+            }
+            ValueTree::Tagged(_, value) => value.round_floats(precision),
+            _ => {}
+        }
+    }
+}
+
+impl PartialEq for ValueTree {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ValueTree::Null, ValueTree::Null) => true,
+            (ValueTree::Bool(a), ValueTree::Bool(b)) => a == b,
+            (ValueTree::Integer(a), ValueTree::Integer(b)) => a == b,
+            (ValueTree::Float(a), ValueTree::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (ValueTree::String(a), ValueTree::String(b)) => a == b,
+            (ValueTree::Sequence(a), ValueTree::Sequence(b)) => a == b,
+            (ValueTree::Mapping(a), ValueTree::Mapping(b)) => {
+                a.len() == b.len() && sort_entries(a) == sort_entries(b)
+            }
+            (ValueTree::Tagged(a_tag, a), ValueTree::Tagged(b_tag, b)) => a_tag == b_tag && a == b,
+            _ => false,
+        }
+    }
+}
+
+impl ValueTree {
+    /// Total order used to sort the entries of mappings.
+    ///
+    /// Consistent with [`PartialEq`] so sorted entries can be compared pairwise.
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (ValueTree::Bool(a), ValueTree::Bool(b)) => a.cmp(b),
+            (ValueTree::Integer(a), ValueTree::Integer(b)) => a.cmp(b),
+            (ValueTree::Float(a), ValueTree::Float(b)) => {
+                normalize_float(*a).total_cmp(&normalize_float(*b))
+            }
+            (ValueTree::String(a), ValueTree::String(b)) => a.cmp(b),
+            (ValueTree::Sequence(a), ValueTree::Sequence(b)) => compare_all(a.iter(), b.iter()),
+            (ValueTree::Mapping(a), ValueTree::Mapping(b)) => compare_all(
+                sort_entries(a)
+                    .into_iter()
+                    .flat_map(|(key, value)| [key, value]),
+                sort_entries(b)
+                    .into_iter()
+                    .flat_map(|(key, value)| [key, value]),
+            ),
+            (ValueTree::Tagged(a_tag, a), ValueTree::Tagged(b_tag, b)) => {
+                a_tag.cmp(b_tag).then_with(|| a.compare(b))
+            }
+            _ => self.get_rank().cmp(&other.get_rank()),
+        }
+    }
+
+    /// Rank of each kind of value in [`ValueTree::compare`].
+    fn get_rank(&self) -> u8 {
+        match self {
+            ValueTree::Null => 0,
+            ValueTree::Bool(_) => 1,
+            ValueTree::Integer(_) => 2,
+            ValueTree::Float(_) => 3,
+            ValueTree::String(_) => 4,
+            ValueTree::Sequence(_) => 5,
+            ValueTree::Mapping(_) => 6,
+            ValueTree::Tagged(..) => 7,
+        }
+    }
+}
+
+/// Treat every NaN and both zeros as equal, as [`PartialEq`] does.
+fn normalize_float(value: f64) -> f64 {
+    if value.is_nan() {
+        f64::NAN
+    } else if value == 0.0 {
+        0.0
+    } else {
+        value
+    }
+}
+
+/// Sort the entries of a mapping by key and then by value.
+fn sort_entries(entries: &[(ValueTree, ValueTree)]) -> Vec<(&ValueTree, &ValueTree)> {
+    let mut sorted: Vec<_> = entries.iter().map(|(key, value)| (key, value)).collect();
+    sorted.sort_by(|(a_key, a_value), (b_key, b_value)| {
+        a_key.compare(b_key).then_with(|| a_value.compare(b_value))
+    });
+    sorted
+}
+
+/// Compare values in order, then by length.
+fn compare_all<'a>(
+    mut a: impl Iterator<Item = &'a ValueTree>,
+    mut b: impl Iterator<Item = &'a ValueTree>,
+) -> Ordering {
+    loop {
+        match (a.next(), b.next()) {
+            (Some(a), Some(b)) => match a.compare(b) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            },
+            (a, b) => return a.is_some().cmp(&b.is_some()),
+        }
+    }
+}
+
+impl Serialize for ValueTree {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ValueTree::Null => serializer.serialize_unit(),
+            ValueTree::Bool(value) => serializer.serialize_bool(*value),
+            ValueTree::Integer(value) => match i64::try_from(*value) {
+                Ok(value) => serializer.serialize_i64(value),
+                Err(_) => {
+                    serializer.serialize_u64(u64::try_from(*value).map_err(S::Error::custom)?)
+                }
+            },
+            ValueTree::Float(value) => serializer.serialize_f64(*value),
+            ValueTree::String(value) => serializer.serialize_str(value),
+            ValueTree::Sequence(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            ValueTree::Mapping(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            ValueTree::Tagged(tag, value) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(tag, value)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ValueTree {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueTreeVisitor)
+    }
+}
+
+struct ValueTreeVisitor;
+
+impl<'de> Visitor<'de> for ValueTreeVisitor {
+    type Value = ValueTree;
+
+    #[allow(clippy::absolute_paths)]
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a serialized value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<ValueTree, E> {
+        Ok(ValueTree::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<ValueTree, E> {
+        Ok(ValueTree::Integer(i128::from(v)))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<ValueTree, E> {
+        Ok(ValueTree::Integer(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<ValueTree, E> {
+        Ok(ValueTree::Integer(i128::from(v)))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<ValueTree, E> {
+        Ok(ValueTree::Float(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<ValueTree, E> {
+        Ok(ValueTree::String(v.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<ValueTree, E> {
+        Ok(ValueTree::String(v))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<ValueTree, E> {
+        Ok(ValueTree::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<ValueTree, E> {
+        Ok(ValueTree::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<ValueTree, D::Error> {
+        ValueTree::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ValueTree, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(ValueTree::Sequence(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ValueTree, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(ValueTree::Mapping(entries))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<ValueTree, A::Error> {
+        let (tag, variant): (String, _) = data.variant()?;
+        let value = variant.newtype_variant()?;
+        Ok(ValueTree::Tagged(tag, Box::new(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn eq_ignores_mapping_order() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = YamlSerializer::default();
+        let a = BufReader::new("a: 1\nb: [2.5, true]\n".as_bytes());
+        let b = BufReader::new("b: [2.5, true]\na: 1\n".as_bytes());
+        let c = BufReader::new("b: [2.5, false]\na: 1\n".as_bytes());
+        // Act
+        let a: ValueTree = serializer.deserialize(a)?;
+        let b: ValueTree = serializer.deserialize(b)?;
+        let c: ValueTree = serializer.deserialize(c)?;
+        // Assert
+        assert_eq!(a, b, "Reordered");
+        assert_ne!(a, c, "Different");
+        Ok(())
+    }
+
+    #[test]
+    fn eq_counts_duplicate_entries() {
+        // Arrange
+        let entry =
+            |key: &str, value: i128| (ValueTree::String(key.to_owned()), ValueTree::Integer(value));
+        let a = ValueTree::Mapping(vec![entry("a", 1), entry("a", 1), entry("b", 2)]);
+        let b = ValueTree::Mapping(vec![entry("b", 2), entry("a", 1), entry("a", 1)]);
+        let c = ValueTree::Mapping(vec![entry("a", 1), entry("b", 2), entry("b", 2)]);
+        // Act
+        // Assert
+        assert_eq!(a, b, "Reordered");
+        assert_ne!(a, c, "Different multiplicities");
+    }
+}
@@ -1,18 +1,31 @@
 use crate::prelude::*;
 
 #[derive(Debug, Default)]
-pub(crate) struct YamlSerializer;
+pub(crate) struct YamlSerializer {
+    options: YamlOptions,
+}
+
+impl YamlSerializer {
+    /// Create a new [`YamlSerializer`].
+    pub(crate) fn new(options: YamlOptions) -> Self {
+        Self { options }
+    }
+}
 
 impl Serializer for YamlSerializer {
-    const EXTENSION: &'static str = "yaml";
+    fn get_extension(&self) -> &'static str {
+        "yaml"
+    }
 
     fn serialize<W: Sized + Write, T: Serialize>(
         &self,
         mut writer: BufWriter<W>,
         value: &T,
     ) -> Result<(), ExpectError> {
-        serde_yaml::to_writer(&mut writer, value)
-            .map_err(|e| ExpectError::SerializeActual(Box::new(e)))?;
+        let yaml = emit_yaml(value, &self.options)?;
+        writer
+            .write_all(yaml.as_bytes())
+            .map_err(ExpectError::WriteActual)?;
         writer.flush().map_err(ExpectError::FlushActual)?;
         Ok(())
     }
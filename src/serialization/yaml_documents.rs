@@ -0,0 +1,358 @@
+use crate::prelude::*;
+use serde::de;
+use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_yaml::Value;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::vec::IntoIter;
+
+const DOCUMENT_START: &str = "---\n";
+
+/// Serialize sequences as YAML with one document per item.
+///
+/// A value that is not a sequence is written as a single document.
+#[derive(Debug, Default)]
+pub(crate) struct YamlDocumentsSerializer {
+    yaml: YamlSerializer,
+}
+
+impl YamlDocumentsSerializer {
+    /// Create a new [`YamlDocumentsSerializer`].
+    pub(crate) fn new(options: YamlOptions) -> Self {
+        Self {
+            yaml: YamlSerializer::new(options),
+        }
+    }
+}
+
+impl Serializer for YamlDocumentsSerializer {
+    fn get_extension(&self) -> &'static str {
+        self.yaml.get_extension()
+    }
+
+    fn get_item_label(&self, index: usize) -> String {
+        format!("Document {}", index + 1)
+    }
+
+    fn serialize<W: Sized + Write, T: Serialize>(
+        &self,
+        mut writer: BufWriter<W>,
+        value: &T,
+    ) -> Result<(), ExpectError> {
+        let mut documents = DocumentsWriter {
+            yaml: &self.yaml,
+            out: String::new(),
+        };
+        write_items(&mut documents, value)?;
+        writer
+            .write_all(documents.out.as_bytes())
+            .map_err(ExpectError::WriteActual)?;
+        writer.flush().map_err(ExpectError::FlushActual)?;
+        Ok(())
+    }
+
+    fn deserialize<R: Read + Sized, T: DeserializeOwned>(
+        &self,
+        mut reader: BufReader<R>,
+    ) -> Result<T, ExpectError> {
+        let mut yaml = String::new();
+        reader
+            .read_to_string(&mut yaml)
+            .map_err(ExpectError::ReadExpected)?;
+        let mut documents = Vec::new();
+        if !is_empty(&yaml) {
+            for document in serde_yaml::Deserializer::from_str(&yaml) {
+                let number = documents.len() + 1;
+                let document = Value::deserialize(document).map_err(|e| {
+                    ExpectError::DeserializeExpected(Box::new(DocumentError(number, e)))
+                })?;
+                documents.push(document);
+            }
+        }
+        let deserializer = DocumentsDeserializer {
+            documents: documents.into_iter(),
+            document: 0,
+        };
+        T::deserialize(deserializer).map_err(|e| ExpectError::DeserializeExpected(Box::new(e)))
+    }
+}
+
+/// Write each item as a YAML document.
+struct DocumentsWriter<'a> {
+    yaml: &'a YamlSerializer,
+    out: String,
+}
+
+impl ItemWriter for DocumentsWriter<'_> {
+    fn write_item<T: ?Sized + Serialize>(&mut self, item: &T) -> Result<(), ExpectError> {
+        self.out.push_str(DOCUMENT_START);
+        self.out.push_str(&self.yaml.serialize_to_string(item)?);
+        Ok(())
+    }
+}
+
+/// Determine if the YAML contains no content other than comments.
+fn is_empty(yaml: &str) -> bool {
+    yaml.lines().all(|line| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with('#')
+    })
+}
+
+/// Deserialize each YAML document as an item of a sequence.
+///
+/// A value that is not a sequence is deserialized from a single document.
+struct DocumentsDeserializer {
+    documents: IntoIter<Value>,
+    /// Number of the most recently read document.
+    document: usize,
+}
+
+impl DocumentsDeserializer {
+    /// Get the only document.
+    fn single(mut self) -> Result<Value, DocumentsError> {
+        match (self.documents.next(), self.documents.next()) {
+            (Some(document), None) => Ok(document),
+            _ => Err(DocumentsError::NotSingleDocument),
+        }
+    }
+}
+
+/// Deserialize a value that is not a sequence from a single document.
+macro_rules! deserialize_single_document {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.single()?
+                    .$method(visitor)
+                    .map_err(|e| DocumentsError::Document(DocumentError(1, e)))
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for DocumentsDeserializer {
+    type Error = DocumentsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self)
+    }
+
+    deserialize_single_document! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_unit
+        deserialize_map deserialize_identifier
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.documents.len() == 1 && self.documents.as_slice().first() == Some(&Value::Null) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single()?
+            .deserialize_unit_struct(name, visitor)
+            .map_err(|e| DocumentsError::Document(DocumentError(1, e)))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single()?
+            .deserialize_struct(name, fields, visitor)
+            .map_err(|e| DocumentsError::Document(DocumentError(1, e)))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single()?
+            .deserialize_enum(name, variants, visitor)
+            .map_err(|e| DocumentsError::Document(DocumentError(1, e)))
+    }
+
+    forward_to_deserialize_any! {
+        seq tuple tuple_struct ignored_any
+    }
+}
+
+impl<'de> SeqAccess<'de> for DocumentsDeserializer {
+    type Error = DocumentsError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some(document) = self.documents.next() else {
+            return Ok(None);
+        };
+        self.document += 1;
+        let item = seed
+            .deserialize(document)
+            .map_err(|e| DocumentsError::Document(DocumentError(self.document, e)))?;
+        Ok(Some(item))
+    }
+}
+
+/// Error deserializing a YAML document.
+#[derive(Debug)]
+struct DocumentError(usize, serde_yaml::Error);
+
+impl Display for DocumentError {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Document {}: {}", self.0, self.1)
+    }
+}
+
+impl Error for DocumentError {}
+
+/// Error deserializing YAML documents.
+#[derive(Debug)]
+enum DocumentsError {
+    Document(DocumentError),
+    /// A value that is not a sequence must be a single document.
+    NotSingleDocument,
+    Custom(String),
+}
+
+impl Display for DocumentsError {
+    #[allow(clippy::absolute_paths)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocumentsError::Document(e) => write!(f, "{e}"),
+            DocumentsError::NotSingleDocument => write!(
+                f,
+                "Expected a single document for a value that is not a sequence"
+            ),
+            DocumentsError::Custom(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Error for DocumentsError {}
+
+impl de::Error for DocumentsError {
+    fn custom<T: Display>(message: T) -> Self {
+        DocumentsError::Custom(message.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = YamlDocumentsSerializer::default();
+        let values = vec![SampleEnum::A, SampleEnum::B];
+        // Act
+        let result = serializer.serialize_to_string(&values)?;
+        // Assert
+        assert_eq!(result, "---\nA\n---\nB\n");
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_empty() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = YamlDocumentsSerializer::default();
+        let values: Vec<SampleEnum> = Vec::new();
+        // Act
+        let result = serializer.serialize_to_string(&values)?;
+        let deserialized: Vec<SampleEnum> =
+            serializer.deserialize(BufReader::new(result.as_bytes()))?;
+        // Assert
+        assert_eq!(result, "");
+        assert!(deserialized.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_not_sequence() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = YamlDocumentsSerializer::default();
+        // Act
+        let result = serializer.serialize_to_string(SampleEnum::A)?;
+        // Assert
+        assert_eq!(result, "---\nA\n");
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = YamlDocumentsSerializer::default();
+        let reader = BufReader::new("---\nA\n---\nC\n".as_bytes());
+        // Act
+        let result: Vec<SampleEnum> = serializer.deserialize(reader)?;
+        // Assert
+        assert_eq!(result, vec![SampleEnum::A, SampleEnum::C]);
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_not_sequence() -> Result<(), ExpectError> {
+        // Arrange
+        let serializer = YamlDocumentsSerializer::default();
+        let sample = SampleStruct::sample();
+        let text = serializer.serialize_to_string(&sample)?;
+        // Act
+        let result: SampleStruct = serializer.deserialize(BufReader::new(text.as_bytes()))?;
+        let none: Option<SampleEnum> =
+            serializer.deserialize(BufReader::new("---\nnull\n".as_bytes()))?;
+        // Assert
+        assert_eq!(result, sample);
+        assert_eq!(none, None);
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_not_sequence_documents() {
+        // Arrange
+        let serializer = YamlDocumentsSerializer::default();
+        let reader = BufReader::new("---\nA\n---\nB\n".as_bytes());
+        // Act
+        let result: Result<SampleEnum, ExpectError> = serializer.deserialize(reader);
+        // Assert
+        let error = result.expect_err("Should fail to deserialize");
+        assert!(error.to_string().contains("single document"), "{error}");
+    }
+
+    #[test]
+    fn deserialize_reports_document() {
+        // Arrange
+        let serializer = YamlDocumentsSerializer::default();
+        let reader = BufReader::new("---\nA\n---\nD\n".as_bytes());
+        // Act
+        let result: Result<Vec<SampleEnum>, ExpectError> = serializer.deserialize(reader);
+        // Assert
+        let error = result.expect_err("Should fail to deserialize");
+        assert!(error.to_string().contains("Document 2:"), "{error}");
+    }
+}
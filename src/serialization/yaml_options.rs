@@ -0,0 +1,292 @@
+use crate::prelude::*;
+use std::iter::repeat_n;
+
+/// Indentation written by `serde_yaml`.
+const YAML_INDENT: usize = 2;
+
+/// Largest indentation of a block scalar indentation indicator.
+const MAX_INDENT: usize = 9;
+
+/// Indicators that start a compact nested node on the same line.
+const COMPACT_INDICATORS: [&str; 3] = ["- ", "? ", ": "];
+
+/// Options for writing YAML.
+///
+/// Multi-line strings are written as literal block scalars.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct YamlOptions {
+    /// Number of spaces per level of indentation.
+    ///
+    /// Values are clamped to `2..=9`, the range of a block scalar indentation indicator.
+    pub indent: usize,
+}
+
+impl Default for YamlOptions {
+    fn default() -> Self {
+        Self {
+            indent: YAML_INDENT,
+        }
+    }
+}
+
+/// Serialize a value to YAML with the [`YamlOptions`].
+pub(crate) fn emit_yaml<T: Serialize + ?Sized>(
+    value: &T,
+    options: &YamlOptions,
+) -> Result<String, ExpectError> {
+    let yaml =
+        serde_yaml::to_string(value).map_err(|e| ExpectError::SerializeActual(Box::new(e)))?;
+    let indent = options.indent.clamp(YAML_INDENT, MAX_INDENT);
+    if indent == YAML_INDENT {
+        return Ok(yaml);
+    }
+    Ok(reindent(&yaml, indent))
+}
+
+/// Widen the indentation of YAML written by `serde_yaml`.
+///
+/// Leading spaces and compact indicators are widened from two spaces to `indent`.
+/// The content of a block scalar moves with its parent but is otherwise copied verbatim.
+fn reindent(yaml: &str, indent: usize) -> String {
+    let mut out = String::with_capacity(yaml.len() * 2);
+    // Original and new indentation of the block scalar content being copied.
+    let mut block: Option<(usize, usize)> = None;
+    for line in yaml.lines() {
+        if let Some((from, to)) = block {
+            if line.is_empty() {
+                out.push('\n');
+                continue;
+            }
+            if count_spaces(line) >= from {
+                push_spaces(&mut out, to);
+                out.push_str(&line[from..]);
+                out.push('\n');
+                continue;
+            }
+            block = None;
+        }
+        let mut column = count_spaces(line);
+        let mut new_column = column.div_euclid(YAML_INDENT) * indent;
+        let mut parent = (column, new_column);
+        push_spaces(&mut out, new_column);
+        let mut rest = &line[column..];
+        while let Some(indicator) = COMPACT_INDICATORS.iter().find(|i| rest.starts_with(*i)) {
+            parent = (column, new_column);
+            out.push_str(indicator.trim_end());
+            push_spaces(&mut out, indent - 1);
+            column += YAML_INDENT;
+            new_column += indent;
+            rest = &rest[YAML_INDENT..];
+        }
+        match rest
+            .rsplit_once(' ')
+            .map_or(("", rest), |(node, header)| (node, header))
+        {
+            (node, header) if is_block_header(header) && is_block_node(node) => {
+                // Block scalar content is indented relative to its parent node.
+                if !strip_tags(node).is_empty() {
+                    parent = (column, new_column);
+                }
+                block = Some((parent.0 + YAML_INDENT, parent.1 + indent));
+                out.push_str(&rest[..rest.len() - header.len()]);
+                for c in header.chars() {
+                    if c.is_ascii_digit() {
+                        out.push_str(&indent.to_string());
+                    } else {
+                        out.push(c);
+                    }
+                }
+            }
+            _ => out.push_str(rest),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Check if the token is a block scalar header such as `|`, `|-` or `|2+`.
+fn is_block_header(token: &str) -> bool {
+    let mut chars = token.chars();
+    matches!(chars.next(), Some('|' | '>'))
+        && chars.all(|c| c.is_ascii_digit() || c == '+' || c == '-')
+}
+
+/// Check if the text before a block scalar header is at most a mapping key and tags.
+///
+/// A plain scalar such as `foo |2` ends with a token that looks like a header but is not.
+fn is_block_node(node: &str) -> bool {
+    let key = strip_tags(node);
+    key.is_empty() || key.ends_with(':')
+}
+
+/// Remove the trailing tags such as `!Variant` from a node.
+fn strip_tags(node: &str) -> &str {
+    let mut node = node.trim_end();
+    loop {
+        let (head, last) = node.rsplit_once(' ').unwrap_or(("", node));
+        if !last.starts_with('!') {
+            return node;
+        }
+        node = head.trim_end();
+    }
+}
+
+fn count_spaces(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn push_spaces(out: &mut String, count: usize) {
+    out.extend(repeat_n(' ', count));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum SampleVariant {
+        Unit,
+        Newtype(String),
+        Tuple(i32, i32),
+        Struct { text: String, values: Vec<i32> },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct SampleText {
+        sql: String,
+        variants: Vec<SampleVariant>,
+        nested: Vec<Vec<String>>,
+        map: BTreeMap<String, SampleVariant>,
+        keys: BTreeMap<Vec<i32>, String>,
+        empty: Vec<i32>,
+        none: Option<i32>,
+    }
+
+    fn sample_text() -> SampleText {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "newtype".to_owned(),
+            SampleVariant::Newtype("a\nb".to_owned()),
+        );
+        map.insert("tuple".to_owned(), SampleVariant::Tuple(1, 2));
+        let mut keys = BTreeMap::new();
+        keys.insert(vec![1, 2], "x\ny".to_owned());
+        SampleText {
+            sql: "SELECT *\nFROM table\nWHERE id = 1\n".to_owned(),
+            variants: vec![
+                SampleVariant::Unit,
+                SampleVariant::Newtype("yes".to_owned()),
+                SampleVariant::Struct {
+                    text: "  leading\nspace".to_owned(),
+                    values: vec![1, 2],
+                },
+            ],
+            nested: vec![vec![" a\nb".to_owned(), "c".to_owned()], vec![]],
+            map,
+            keys,
+            empty: Vec::new(),
+            none: None,
+        }
+    }
+
+    fn edge_strings() -> Vec<String> {
+        [
+            "", "\n", "\n\n", " x", "x ", "a\n ", "\t", "\ta\n", "a\tb\nc", "\na", "a\n\n",
+            "x\n  y\n", "- a\n- b", "foo |2", "foo >1-",
+        ]
+        .iter()
+        .map(|s| (*s).to_owned())
+        .collect()
+    }
+
+    #[test]
+    fn emit_yaml_default() -> Result<(), ExpectError> {
+        // Arrange
+        let value = sample_text();
+        // Act
+        let result = emit_yaml(&value, &YamlOptions::default())?;
+        // Assert
+        let expected = serde_yaml::to_string(&value).expect("Should serialize");
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn emit_yaml_indent() -> Result<(), ExpectError> {
+        // Arrange
+        let options = YamlOptions { indent: 4 };
+        let value = sample_text();
+        // Act
+        let result = emit_yaml(&value, &options)?;
+        // Assert
+        assert!(
+            result.contains("sql: |\n    SELECT *\n    FROM table\n"),
+            "{result}"
+        );
+        assert!(
+            result.contains("nested:\n-   -   |4-\n         a\n        b\n"),
+            "{result}"
+        );
+        let deserialized: SampleText = serde_yaml::from_str(&result).expect("Should deserialize");
+        assert_eq!(deserialized, value);
+        Ok(())
+    }
+
+    #[test]
+    fn emit_yaml_plain_header() -> Result<(), ExpectError> {
+        // Arrange
+        let options = YamlOptions { indent: 4 };
+        let mut value = BTreeMap::new();
+        value.insert("key".to_owned(), vec!["foo |2".to_owned()]);
+        value.insert("other".to_owned(), vec!["bar >1-".to_owned()]);
+        // Act
+        let result = emit_yaml(&value, &options)?;
+        // Assert
+        assert!(result.contains("key:\n-   foo |2\n"), "{result}");
+        let deserialized: BTreeMap<String, Vec<String>> =
+            serde_yaml::from_str(&result).expect("Should deserialize");
+        assert_eq!(deserialized, value);
+        let result = emit_yaml(&BTreeMap::from([("key", "foo |2")]), &options)?;
+        assert_eq!(result, "key: foo |2\n");
+        Ok(())
+    }
+
+    #[test]
+    fn emit_yaml_round_trip() -> Result<(), ExpectError> {
+        for indent in [0, 2, 3, 4, 9, 12] {
+            // Arrange
+            let options = YamlOptions { indent };
+            let value = sample_text();
+            let strings = edge_strings();
+            let mut map = BTreeMap::new();
+            map.insert("text".to_owned(), strings.clone());
+            // Act
+            let result = emit_yaml(&value, &options)?;
+            let strings_result = emit_yaml(&strings, &options)?;
+            let map_result = emit_yaml(&map, &options)?;
+            // Assert
+            let deserialized: SampleText =
+                serde_yaml::from_str(&result).expect("Should deserialize");
+            assert_eq!(deserialized, value, "Indent {indent}");
+            let deserialized: Vec<String> =
+                serde_yaml::from_str(&strings_result).expect("Should deserialize");
+            assert_eq!(deserialized, strings, "Indent {indent}");
+            let deserialized: BTreeMap<String, Vec<String>> =
+                serde_yaml::from_str(&map_result).expect("Should deserialize");
+            assert_eq!(deserialized, map, "Indent {indent}");
+            for text in &strings {
+                let result = emit_yaml(text, &options)?;
+                let deserialized: String =
+                    serde_yaml::from_str(&result).expect("Should deserialize");
+                assert_eq!(&deserialized, text, "Indent {indent}");
+            }
+        }
+        Ok(())
+    }
+}
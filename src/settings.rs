@@ -0,0 +1,144 @@
+use crate::prelude::*;
+use std::env;
+use std::ffi::OsStr;
+
+/// Settings of an [`Expect`].
+#[derive(Clone, Debug)]
+pub(crate) struct Settings {
+    /// Name of the snapshot directory in the directory of the test module.
+    pub(crate) snapshot_dir: String,
+    /// Directory of every snapshot mirroring the source tree.
+    ///
+    /// If set the snapshot directory is not used.
+    pub(crate) snapshot_root: Option<PathBuf>,
+    /// Directory of the actual results files mirroring the snapshot layout.
+    ///
+    /// If not set the actual results files are written alongside the expected results files.
+    pub(crate) actual_dir: Option<PathBuf>,
+    /// Only keep the actual results file of a mismatch.
+    pub(crate) actual_on_mismatch: bool,
+    /// Extension inserted before the file extension of actual results.
+    pub(crate) actual_ext: String,
+    /// Extension inserted before the file extension of expected results.
+    pub(crate) expected_ext: String,
+    /// Format of serialized snapshots.
+    #[cfg(feature = "serde")]
+    pub(crate) format: Format,
+    /// Options for writing YAML.
+    #[cfg(feature = "yaml")]
+    pub(crate) yaml: YamlOptions,
+    /// Options for writing JSON.
+    #[cfg(feature = "json")]
+    pub(crate) json: JsonOptions,
+    /// Number of decimal places floats are rounded to before serialized values are compared.
+    #[cfg(feature = "serde")]
+    pub(crate) float_precision: Option<u8>,
+    /// When the expected results file is written from the actual results.
+    pub(crate) update: UpdateMode,
+    /// Never write the expected results file when running in CI.
+    pub(crate) ci_strict: bool,
+    /// Create the snapshot directory of the module if it does not exist.
+    ///
+    /// If not set the directory is created unless running in CI.
+    pub(crate) create_dirs: Option<bool>,
+    /// Scrubbers applied to text before it is compared.
+    pub(crate) scrubbers: Vec<Scrubber>,
+    /// Options for printing the differences of a mismatch.
+    pub(crate) diff: DiffOptions,
+    /// Collect mismatches and panic when finished.
+    pub(crate) soft: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            snapshot_dir: EXPECT_DIR.to_owned(),
+            snapshot_root: None,
+            actual_dir: None,
+            actual_on_mismatch: false,
+            actual_ext: ACTUAL_EXT.to_owned(),
+            expected_ext: EXPECT_EXT.to_owned(),
+            #[cfg(feature = "serde")]
+            format: Format::default(),
+            #[cfg(feature = "yaml")]
+            yaml: YamlOptions::default(),
+            #[cfg(feature = "json")]
+            json: JsonOptions::default(),
+            #[cfg(feature = "serde")]
+            float_precision: None,
+            update: UpdateMode::default(),
+            ci_strict: false,
+            create_dirs: None,
+            scrubbers: Vec::new(),
+            diff: DiffOptions::default(),
+            soft: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Get the [`Settings`] bound to the current scope.
+    ///
+    /// Otherwise the default [`Settings`] with the project configuration applied.
+    pub(crate) fn load() -> Self {
+        if let Some(settings) = get_scoped_settings() {
+            return settings;
+        }
+        #[allow(unused_mut)]
+        let mut settings = Self::default();
+        #[cfg(feature = "config")]
+        Config::get().apply(&mut settings);
+        settings
+    }
+
+    /// Get the [`UpdateMode`] taking CI strictness into account.
+    pub(crate) fn get_update_mode(&self) -> UpdateMode {
+        if self.ci_strict && is_running_in_ci() {
+            UpdateMode::Never
+        } else {
+            self.update
+        }
+    }
+
+    /// Should values be compared in serialized form?
+    ///
+    /// Floats are rounded and scrubbers are applied to the serialized text.
+    #[cfg(feature = "serde")]
+    pub(crate) fn should_compare_serialized(&self) -> bool {
+        self.float_precision.is_some() || !self.scrubbers.is_empty()
+    }
+
+    /// Should the snapshot directory of the module be created if it does not exist?
+    pub(crate) fn should_create_dirs(&self) -> bool {
+        self.create_dirs.unwrap_or_else(|| !is_running_in_ci())
+    }
+}
+
+/// Is the current process running in CI?
+///
+/// CI is detected by the `CI` environment variable.
+fn is_running_in_ci() -> bool {
+    is_ci(env::var_os("CI").as_deref())
+}
+
+/// Is the value of the `CI` environment variable set to a truthy value?
+fn is_ci(value: Option<&OsStr>) -> bool {
+    value.is_some_and(|value| !value.is_empty() && value != "0" && value != "false")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_ci_values() {
+        // Arrange
+        // Act
+        // Assert
+        assert!(is_ci(Some(OsStr::new("true"))));
+        assert!(is_ci(Some(OsStr::new("1"))));
+        assert!(!is_ci(Some(OsStr::new("false"))));
+        assert!(!is_ci(Some(OsStr::new(""))));
+        assert!(!is_ci(None));
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(feature = "config")]
+use serde::Deserialize;
+
+/// When the expected results file is written from the actual results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum UpdateMode {
+    /// Create the expected results file if it does not exist.
+    #[default]
+    Missing,
+    /// Always overwrite the expected results file.
+    Always,
+    /// Never write the expected results file.
+    ///
+    /// A missing expected results file is an error.
+    Never,
+}